@@ -0,0 +1,57 @@
+use bip_tools::engine::Engine;
+use bip_tools::{IndexRange, ScriptType, Xpub};
+use criterion::{criterion_group, criterion_main, Criterion};
+use secp256k1::Secp256k1;
+
+// Same account key as the BIP44 test vectors
+const TEST_XPUB: &str = "xpub6CQrEh7fCh2jd4kdgqCxAQ4dpzvLGCmx5PM3GLQH1bQRCLWRUMHqeZ5XWi8QUM39BeFeBJaUA5VS4Vvw5oLaA6tHZBifTetFCxj6keSvfFS";
+
+// Number of addresses derived per iteration
+const COUNT: u32 = 100_000;
+
+fn bulk_derivation(c: &mut Criterion) {
+    let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+    let range = IndexRange::from_count(0, COUNT).unwrap();
+
+    let mut group = c.benchmark_group("derive_100k_p2wpkh");
+    group.sample_size(10);
+
+    // Previous behavior: a new context for every child key
+    group.bench_function("context_per_child", |b| {
+        b.iter(|| {
+            range
+                .iter()
+                .map(|i| {
+                    let engine = Engine::from_context(Secp256k1::new());
+                    let child = engine.derive_non_hardened(&xpub, i).unwrap();
+                    engine.address(&child, ScriptType::P2wpkh)
+                })
+                .count()
+        })
+    });
+
+    // Shared global engine, as used by derive_bip32_addresses
+    group.bench_function("global_engine", |b| {
+        b.iter(|| {
+            xpub.derive_bip32_address_range(range, ScriptType::P2wpkh)
+                .unwrap()
+                .len()
+        })
+    });
+
+    // Caller owned verification-only engine
+    let engine = Engine::verification_only();
+    group.bench_function("verification_only_engine", |b| {
+        b.iter(|| {
+            engine
+                .address_iter(&xpub, range, ScriptType::P2wpkh)
+                .unwrap()
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bulk_derivation);
+criterion_main!(benches);
@@ -0,0 +1,336 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{base58check, bech32};
+use crate::{Chain, DerivationPath, Error, Network, Script};
+
+/// Output script types supported for single key address generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptType {
+    /// Legacy pay to public key hash, Base58Check encoded (1... / m... / n...)
+    P2pkh,
+    /// Segwit v0 P2WPKH nested in pay to script hash, Base58Check encoded (3... / 2...)
+    P2shP2wpkh,
+    /// Native segwit v0 pay to witness public key hash, bech32 encoded (bc1q...)
+    P2wpkh,
+    /// Taproot key path spend with the BIP86 tweak, bech32m encoded (bc1p...)
+    P2tr,
+}
+
+impl ScriptType {
+    /// All supported script types
+    pub const ALL: [ScriptType; 4] = [
+        ScriptType::P2pkh,
+        ScriptType::P2shP2wpkh,
+        ScriptType::P2wpkh,
+        ScriptType::P2tr,
+    ];
+
+    /// Detects the script type of a single key address on the given network
+    /// P2SH addresses are assumed to be nested P2WPKH, the only single key P2SH type
+    pub fn from_address(address: &str, network: Network) -> Result<Self, Error> {
+        match Address::parse_for_network(address, network)?.address_type() {
+            AddressType::P2pkh => Ok(ScriptType::P2pkh),
+            AddressType::P2sh => Ok(ScriptType::P2shP2wpkh),
+            AddressType::P2wpkh => Ok(ScriptType::P2wpkh),
+            AddressType::P2tr => Ok(ScriptType::P2tr),
+            address_type => Err(Error::UnsupportedAddressType(address_type)),
+        }
+    }
+
+    /// Name of the script type as accepted by `FromStr`
+    pub fn name(self) -> &'static str {
+        match self {
+            ScriptType::P2pkh => "p2pkh",
+            ScriptType::P2shP2wpkh => "p2sh-p2wpkh",
+            ScriptType::P2wpkh => "p2wpkh",
+            ScriptType::P2tr => "p2tr",
+        }
+    }
+}
+
+impl fmt::Display for ScriptType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ScriptType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "p2pkh" | "legacy" => Ok(ScriptType::P2pkh),
+            "p2sh-p2wpkh" | "p2sh-segwit" | "nested" => Ok(ScriptType::P2shP2wpkh),
+            "p2wpkh" | "segwit" | "bech32" => Ok(ScriptType::P2wpkh),
+            "p2tr" | "taproot" | "bech32m" => Ok(ScriptType::P2tr),
+            _ => Err(Error::UnknownScriptType(s.to_string())),
+        }
+    }
+}
+
+/// Location of an address found below an account key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AddressMatch {
+    pub chain: Chain,            // Account chain of the address
+    pub index: u32,              // Child index on the chain
+    pub script_type: ScriptType, // Script type of the address
+}
+
+impl AddressMatch {
+    /// Path of the address relative to the account key (chain/index)
+    pub fn path(&self) -> DerivationPath {
+        DerivationPath::new(vec![self.chain.index(), self.index])
+    }
+}
+
+impl fmt::Display for AddressMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({} chain, index {}, {})",
+            self.path().to_relative_string(),
+            self.chain,
+            self.index,
+            self.script_type
+        )
+    }
+}
+
+/// Kind of output an address pays to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressType {
+    /// Pay to public key hash, Base58Check encoded
+    P2pkh,
+    /// Pay to script hash, Base58Check encoded (nested segwit and legacy multisig)
+    P2sh,
+    /// Segwit v0 pay to witness public key hash (20 byte program)
+    P2wpkh,
+    /// Segwit v0 pay to witness script hash (32 byte program)
+    P2wsh,
+    /// Segwit v1 pay to Taproot (32 byte program)
+    P2tr,
+    /// Witness program without a defined output type yet, spendable by anyone today
+    WitnessUnknown,
+}
+
+impl AddressType {
+    /// Name of the address type (e.g. "p2wpkh")
+    pub fn name(self) -> &'static str {
+        match self {
+            AddressType::P2pkh => "p2pkh",
+            AddressType::P2sh => "p2sh",
+            AddressType::P2wpkh => "p2wpkh",
+            AddressType::P2wsh => "p2wsh",
+            AddressType::P2tr => "p2tr",
+            AddressType::WitnessUnknown => "witness-unknown",
+        }
+    }
+}
+
+impl fmt::Display for AddressType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Data an address commits to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Payload {
+    /// HASH160 of a public key (P2PKH)
+    PubkeyHash([u8; 20]),
+    /// HASH160 of a redeem script (P2SH)
+    ScriptHash([u8; 20]),
+    /// Segwit witness version (0 to 16) and program (2 to 40 bytes)
+    WitnessProgram { version: u8, program: Vec<u8> },
+}
+
+/// A parsed and validated Bitcoin address
+/// Base58Check P2PKH/P2SH, bech32 segwit v0 and bech32m segwit v1+ addresses are supported
+/// Test networks share address encodings, so `network` is the first matching network:
+/// testnet for Base58 test prefixes and "tb", regtest for "bcrt"
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Address {
+    network: Network, // Network the address was parsed for
+    payload: Payload, // Hash or witness program the address commits to
+}
+
+impl Address {
+    /// Builds an address for the given network from its payload
+    /// Witness programs are checked like decoded ones, so every address can be encoded
+    pub fn new(network: Network, payload: Payload) -> Result<Self, Error> {
+        if let Payload::WitnessProgram { version, program } = &payload {
+            bech32::check_witness_program(*version, program)?;
+        }
+        Ok(Self { network, payload })
+    }
+
+    /// Parses an address and detects its network from the prefix or human readable part
+    /// Bech32 addresses must not mix upper and lower case, and every checksum is verified
+    pub fn parse(address: &str) -> Result<Self, Error> {
+        let lowercase = address.to_ascii_lowercase();
+        let bech32_network = [Network::Regtest, Network::Bitcoin, Network::Testnet]
+            .into_iter()
+            .find(|network| lowercase.starts_with(&format!("{}1", network.bech32_hrp())));
+
+        match bech32_network {
+            Some(network) => Self::parse_segwit(address, network),
+            None => Self::parse_base58(address),
+        }
+    }
+
+    /// Parses an address and checks that it belongs to the given network
+    pub fn parse_for_network(address: &str, network: Network) -> Result<Self, Error> {
+        Self::parse(address)?.with_network(network)
+    }
+
+    /// Decodes a bech32 or bech32m segwit address
+    fn parse_segwit(address: &str, network: Network) -> Result<Self, Error> {
+        let (version, program) = bech32::decode_segwit_address(network.bech32_hrp(), address)?;
+        Ok(Self {
+            network,
+            payload: Payload::WitnessProgram { version, program },
+        })
+    }
+
+    /// Decodes a Base58Check P2PKH or P2SH address
+    fn parse_base58(address: &str) -> Result<Self, Error> {
+        let payload = base58check::decode_check(address)?;
+        if payload.len() != 21 {
+            return Err(Error::InvalidLength {
+                expected: 21,
+                found: payload.len(),
+            });
+        }
+
+        let hash: [u8; 20] = payload[1..].try_into().unwrap();
+        for network in [Network::Bitcoin, Network::Testnet] {
+            if payload[0] == network.p2pkh_prefix() {
+                return Ok(Self {
+                    network,
+                    payload: Payload::PubkeyHash(hash),
+                });
+            }
+            if payload[0] == network.p2sh_prefix() {
+                return Ok(Self {
+                    network,
+                    payload: Payload::ScriptHash(hash),
+                });
+            }
+        }
+
+        Err(Error::UnknownAddressVersion(payload[0]))
+    }
+
+    /// Network the address was parsed for
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Hash or witness program the address commits to
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    /// Returns a copy of this address bound to the given network
+    /// Fails when the network uses other address prefixes or another human readable part
+    pub fn with_network(mut self, network: Network) -> Result<Self, Error> {
+        if !self.is_valid_for_network(network) {
+            return Err(Error::AddressNetworkMismatch {
+                address: self.network,
+                network,
+            });
+        }
+        self.network = network;
+        Ok(self)
+    }
+
+    /// Returns true when the address encoding is used on the given network
+    pub fn is_valid_for_network(&self, network: Network) -> bool {
+        match self.payload {
+            Payload::PubkeyHash(_) | Payload::ScriptHash(_) => {
+                network.p2pkh_prefix() == self.network.p2pkh_prefix()
+            }
+            Payload::WitnessProgram { .. } => network.bech32_hrp() == self.network.bech32_hrp(),
+        }
+    }
+
+    /// All networks the address is valid on
+    pub fn networks(&self) -> Vec<Network> {
+        Network::ALL
+            .into_iter()
+            .filter(|network| self.is_valid_for_network(*network))
+            .collect()
+    }
+
+    /// Kind of output the address pays to
+    pub fn address_type(&self) -> AddressType {
+        match &self.payload {
+            Payload::PubkeyHash(_) => AddressType::P2pkh,
+            Payload::ScriptHash(_) => AddressType::P2sh,
+            Payload::WitnessProgram {
+                version: 0,
+                program,
+            } if program.len() == 20 => AddressType::P2wpkh,
+            Payload::WitnessProgram { version: 0, .. } => AddressType::P2wsh,
+            Payload::WitnessProgram {
+                version: 1,
+                program,
+            } if program.len() == 32 => AddressType::P2tr,
+            Payload::WitnessProgram { .. } => AddressType::WitnessUnknown,
+        }
+    }
+
+    /// Segwit witness version, or None for Base58 addresses
+    pub fn witness_version(&self) -> Option<u8> {
+        match self.payload {
+            Payload::WitnessProgram { version, .. } => Some(version),
+            _ => None,
+        }
+    }
+
+    /// Builds the output script (scriptPubKey) the address pays to
+    /// P2PKH: OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+    /// P2SH: OP_HASH160 <20 bytes> OP_EQUAL
+    /// Segwit: OP_n <program>, with OP_0 for version 0 and OP_1 to OP_16 above
+    pub fn script_pubkey(&self) -> Script {
+        match &self.payload {
+            Payload::PubkeyHash(hash) => Script::p2pkh(hash),
+            Payload::ScriptHash(hash) => Script::p2sh(hash),
+            Payload::WitnessProgram { version, program } => {
+                Script::witness_program(*version, program)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    /// Encodes the address, with bech32 addresses in lowercase
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = match &self.payload {
+            Payload::PubkeyHash(hash) => base58_address(self.network.p2pkh_prefix(), hash),
+            Payload::ScriptHash(hash) => base58_address(self.network.p2sh_prefix(), hash),
+            Payload::WitnessProgram { version, program } => {
+                bech32::encode_segwit_address(self.network.bech32_hrp(), *version, program)
+                    .expect("witness programs are checked when the address is built")
+            }
+        };
+        f.write_str(&encoded)
+    }
+}
+
+impl FromStr for Address {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Base58Check encodes a version byte followed by a 20 byte hash
+pub(crate) fn base58_address(prefix: u8, hash: &[u8; 20]) -> String {
+    let mut payload = [0u8; 21];
+    payload[0] = prefix;
+    payload[1..].copy_from_slice(hash);
+    base58check::encode_check(&payload)
+}
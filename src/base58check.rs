@@ -0,0 +1,47 @@
+use base58::{FromBase58, ToBase58};
+
+use crate::hash::sha256d;
+use crate::Error;
+
+/// Length of the checksum appended to Base58Check payloads
+pub const CHECKSUM_LEN: usize = 4;
+
+/// Encodes a payload as Base58Check: payload || first 4 bytes of SHA256d(payload)
+pub fn encode_check(payload: &[u8]) -> String {
+    let mut data = Vec::with_capacity(payload.len() + CHECKSUM_LEN);
+    data.extend_from_slice(payload);
+    data.extend_from_slice(&sha256d(payload)[..CHECKSUM_LEN]);
+    data.to_base58()
+}
+
+/// Decodes a Base58Check string and returns the payload without its checksum
+pub fn decode_check(encoded: &str) -> Result<Vec<u8>, Error> {
+    let decoded = encoded.from_base58()?;
+
+    let payload_len = verify_checksum(&decoded)?.len();
+    let mut payload = decoded;
+    payload.truncate(payload_len);
+    Ok(payload)
+}
+
+/// Verifies the trailing checksum of already decoded Base58Check data
+/// Returns the payload slice when the checksum matches
+pub fn verify_checksum(data: &[u8]) -> Result<&[u8], Error> {
+    if data.len() < CHECKSUM_LEN {
+        return Err(Error::InvalidLength {
+            expected: CHECKSUM_LEN,
+            found: data.len(),
+        });
+    }
+
+    let (payload, checksum) = data.split_at(data.len() - CHECKSUM_LEN);
+    let expected: [u8; CHECKSUM_LEN] = sha256d(payload)[..CHECKSUM_LEN].try_into().unwrap();
+    if checksum != expected {
+        return Err(Error::ChecksumMismatch {
+            expected,
+            found: checksum.try_into().unwrap(),
+        });
+    }
+
+    Ok(payload)
+}
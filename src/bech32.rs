@@ -0,0 +1,258 @@
+use crate::Error;
+
+/// Bech32 character set, mapping 5-bit values to characters
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Generator coefficients of the BCH checksum
+const GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+/// Constant the checksum polymod must equal for a valid bech32 string (BIP173)
+const BECH32_CONST: u32 = 1;
+
+/// Constant the checksum polymod must equal for a valid bech32m string (BIP350)
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Maximum length of a bech32 string
+const MAX_LENGTH: usize = 90;
+
+/// Length of the checksum in characters
+const CHECKSUM_LENGTH: usize = 6;
+
+/// Checksum variant of a bech32 string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Original checksum from BIP173, used by witness version 0
+    Bech32,
+    /// Modified checksum from BIP350, used by witness versions 1 through 16
+    Bech32m,
+}
+
+impl Variant {
+    /// Constant the checksum polymod is compared against
+    fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => BECH32_CONST,
+            Variant::Bech32m => BECH32M_CONST,
+        }
+    }
+
+    /// Variant required for addresses with the given witness version
+    pub fn for_witness_version(version: u8) -> Self {
+        if version == 0 {
+            Variant::Bech32
+        } else {
+            Variant::Bech32m
+        }
+    }
+}
+
+/// Computes the BCH checksum polymod over 5-bit values
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ u32::from(*value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+/// Expands the human readable part for use in checksum computation
+fn hrp_expand(hrp: &[u8]) -> Vec<u8> {
+    let mut expanded = Vec::with_capacity(hrp.len() * 2 + 1);
+    expanded.extend(hrp.iter().map(|c| c >> 5));
+    expanded.push(0);
+    expanded.extend(hrp.iter().map(|c| c & 0x1f));
+    expanded
+}
+
+/// Creates the 6 character checksum for the given hrp and data
+fn create_checksum(hrp: &[u8], data: &[u8], variant: Variant) -> [u8; CHECKSUM_LENGTH] {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; CHECKSUM_LENGTH]);
+    let polymod = polymod(&values) ^ variant.constant();
+
+    let mut checksum = [0u8; CHECKSUM_LENGTH];
+    for (i, c) in checksum.iter_mut().enumerate() {
+        *c = ((polymod >> (5 * (5 - i))) & 0x1f) as u8;
+    }
+    checksum
+}
+
+/// Verifies the checksum of the given hrp and data (including checksum)
+/// Returns the variant the checksum was created with
+fn verify_checksum(hrp: &[u8], data: &[u8]) -> Option<Variant> {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    match polymod(&values) {
+        BECH32_CONST => Some(Variant::Bech32),
+        BECH32M_CONST => Some(Variant::Bech32m),
+        _ => None,
+    }
+}
+
+/// Encodes a human readable part and 5-bit data values as a bech32 or bech32m string
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<String, Error> {
+    if hrp.is_empty() || hrp.bytes().any(|c| !(33..=126).contains(&c)) {
+        return Err(Error::Bech32InvalidHrp(hrp.to_string()));
+    }
+    if data.iter().any(|v| *v > 31) {
+        return Err(Error::Bech32InvalidData);
+    }
+    let len = hrp.len() + 1 + data.len() + CHECKSUM_LENGTH;
+    if len > MAX_LENGTH {
+        return Err(Error::Bech32TooLong(len));
+    }
+
+    let hrp = hrp.to_ascii_lowercase();
+    let checksum = create_checksum(hrp.as_bytes(), data, variant);
+
+    let mut encoded = hrp;
+    encoded.push('1');
+    for value in data.iter().chain(checksum.iter()) {
+        encoded.push(CHARSET[*value as usize] as char);
+    }
+    Ok(encoded)
+}
+
+/// Decodes a bech32 or bech32m string into its human readable part, 5-bit data values
+/// and checksum variant
+/// The returned data excludes the checksum and the hrp is returned in lowercase
+pub fn decode(encoded: &str) -> Result<(String, Vec<u8>, Variant), Error> {
+    if encoded.len() > MAX_LENGTH {
+        return Err(Error::Bech32TooLong(encoded.len()));
+    }
+    if let Some(c) = encoded.chars().find(|c| !(33..=126).contains(&(*c as u32))) {
+        return Err(Error::Bech32InvalidCharacter(c));
+    }
+
+    let has_lower = encoded.bytes().any(|c| c.is_ascii_lowercase());
+    let has_upper = encoded.bytes().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(Error::Bech32MixedCase);
+    }
+    let encoded = encoded.to_ascii_lowercase();
+
+    let separator = encoded.rfind('1').ok_or(Error::Bech32MissingSeparator)?;
+    if separator == 0 {
+        return Err(Error::Bech32InvalidHrp(String::new()));
+    }
+    if separator + 1 + CHECKSUM_LENGTH > encoded.len() {
+        return Err(Error::Bech32Checksum);
+    }
+
+    let (hrp, rest) = encoded.split_at(separator);
+    let mut data = rest[1..]
+        .bytes()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|x| *x == c)
+                .map(|v| v as u8)
+                .ok_or(Error::Bech32InvalidCharacter(c as char))
+        })
+        .collect::<Result<Vec<u8>, Error>>()?;
+
+    let variant = verify_checksum(hrp.as_bytes(), &data).ok_or(Error::Bech32Checksum)?;
+
+    data.truncate(data.len() - CHECKSUM_LENGTH);
+    Ok((hrp.to_string(), data, variant))
+}
+
+/// Regroups bits between word sizes, e.g. 8-bit bytes to 5-bit bech32 values
+/// With `pad` set, incomplete trailing groups are zero padded, otherwise they must be zero
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Error> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value: u32 = (1 << to) - 1;
+    let mut converted = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for value in data {
+        let value = u32::from(*value);
+        if value >> from != 0 {
+            return Err(Error::Bech32InvalidData);
+        }
+        acc = (acc << from) | value;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            converted.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            converted.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max_value) != 0 {
+        return Err(Error::Bech32InvalidData);
+    }
+
+    Ok(converted)
+}
+
+/// Checks the witness version and program length rules from BIP141, BIP173 and BIP350
+pub(crate) fn check_witness_program(version: u8, program: &[u8]) -> Result<(), Error> {
+    if version > 16 {
+        return Err(Error::InvalidWitnessVersion(version));
+    }
+    if program.len() < 2
+        || program.len() > 40
+        || (version == 0 && !matches!(program.len(), 20 | 32))
+    {
+        return Err(Error::InvalidWitnessProgramLength {
+            version,
+            length: program.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Encodes a segwit address from its human readable part, witness version and program
+/// Version 0 uses bech32 and versions 1 and above use bech32m (BIP350)
+pub fn encode_segwit_address(hrp: &str, version: u8, program: &[u8]) -> Result<String, Error> {
+    check_witness_program(version, program)?;
+
+    let mut data = vec![version];
+    data.extend(convert_bits(program, 8, 5, true)?);
+    encode(hrp, &data, Variant::for_witness_version(version))
+}
+
+/// Decodes a segwit address, checking it uses the expected human readable part
+/// Returns the witness version and witness program
+pub fn decode_segwit_address(hrp: &str, address: &str) -> Result<(u8, Vec<u8>), Error> {
+    let (decoded_hrp, data, variant) = decode(address)?;
+    if decoded_hrp != hrp.to_ascii_lowercase() {
+        return Err(Error::WrongHrp {
+            expected: hrp.to_ascii_lowercase(),
+            found: decoded_hrp,
+        });
+    }
+
+    let (version, program) = data.split_first().ok_or(Error::Bech32InvalidData)?;
+    if *version > 16 {
+        return Err(Error::InvalidWitnessVersion(*version));
+    }
+    if variant != Variant::for_witness_version(*version) {
+        return Err(Error::WitnessVariantMismatch {
+            version: *version,
+            expected: Variant::for_witness_version(*version),
+            found: variant,
+        });
+    }
+    let program = convert_bits(program, 5, 8, false)?;
+    check_witness_program(*version, &program)?;
+
+    Ok((*version, program))
+}
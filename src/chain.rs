@@ -0,0 +1,82 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Chain of a BIP44 style account (BIP44, BIP49, BIP84 and BIP86 share the layout)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chain {
+    /// External chain (account/0/i) used for receiving payments
+    External,
+    /// Internal chain (account/1/i) used for change outputs
+    Internal,
+}
+
+impl Chain {
+    /// Child index of the chain below the account key
+    pub fn index(self) -> u32 {
+        match self {
+            Chain::External => 0,
+            Chain::Internal => 1,
+        }
+    }
+
+    /// Name of the chain used in labels
+    pub fn name(self) -> &'static str {
+        match self {
+            Chain::External => "external",
+            Chain::Internal => "internal",
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Selects which account chains to derive addresses from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChainSelection {
+    /// Receive addresses only
+    #[default]
+    External,
+    /// Change addresses only
+    Internal,
+    /// Receive addresses followed by change addresses
+    Both,
+}
+
+impl ChainSelection {
+    /// Chains covered by the selection, in derivation order
+    pub fn chains(self) -> &'static [Chain] {
+        match self {
+            ChainSelection::External => &[Chain::External],
+            ChainSelection::Internal => &[Chain::Internal],
+            ChainSelection::Both => &[Chain::External, Chain::Internal],
+        }
+    }
+}
+
+impl From<Chain> for ChainSelection {
+    fn from(chain: Chain) -> Self {
+        match chain {
+            Chain::External => ChainSelection::External,
+            Chain::Internal => ChainSelection::Internal,
+        }
+    }
+}
+
+impl FromStr for ChainSelection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "external" | "receive" | "0" => Ok(ChainSelection::External),
+            "internal" | "change" | "1" => Ok(ChainSelection::Internal),
+            "both" | "all" => Ok(ChainSelection::Both),
+            _ => Err(Error::UnknownChain(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use crate::{Network, Version, Xpub};
+
+/// An extended public key re-encoded under another SLIP-132 version
+/// The key material (depth, fingerprints, chain code and public key) is unchanged,
+/// only the version bytes and the implied network and script type differ
+#[derive(Clone)]
+pub struct Conversion {
+    pub from: Version,         // Version of the original key
+    pub from_network: Network, // Network the original key was parsed for
+    pub xpub: Xpub,            // Key with the target version
+}
+
+impl Conversion {
+    /// Re-encodes the key under the given version
+    /// The network falls back to the version's default when the network class changes
+    pub fn new(xpub: &Xpub, version: Version) -> Self {
+        Self {
+            from: xpub.version,
+            from_network: xpub.network,
+            xpub: xpub.clone().with_version(version),
+        }
+    }
+
+    /// Version of the converted key
+    pub fn to(&self) -> Version {
+        self.xpub.version
+    }
+
+    /// Returns true when the target version implies a different script type
+    /// Wallets importing the converted key would derive different addresses
+    pub fn changes_script_type(&self) -> bool {
+        self.from.script_type_name() != self.to().script_type_name()
+    }
+
+    /// Returns true when the conversion crosses between mainnet and the test networks
+    pub fn changes_network(&self) -> bool {
+        self.from.is_mainnet() != self.to().is_mainnet()
+    }
+
+    /// Warnings about what the conversion changes besides the encoding
+    pub fn warnings(&self) -> Vec<ConversionWarning> {
+        let mut warnings = Vec::new();
+        if self.changes_script_type() {
+            warnings.push(ConversionWarning::ScriptTypeChanged {
+                from: self.from,
+                to: self.to(),
+            });
+        }
+        if self.changes_network() {
+            warnings.push(ConversionWarning::NetworkChanged {
+                from: self.from_network,
+                to: self.xpub.network,
+            });
+        }
+        warnings
+    }
+
+    /// Base58 encoding of the converted key
+    pub fn to_base58(&self) -> String {
+        self.xpub.to_base58()
+    }
+}
+
+/// Side effect of a SLIP-132 conversion that the caller should be told about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionWarning {
+    /// The target version implies another script type (e.g. zpub to xpub: p2wpkh to p2pkh)
+    ScriptTypeChanged { from: Version, to: Version },
+    /// The target version belongs to the other network class (e.g. xpub to tpub)
+    NetworkChanged { from: Network, to: Network },
+}
+
+impl fmt::Display for ConversionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionWarning::ScriptTypeChanged { from, to } => write!(
+                f,
+                "implied script type changes from {} ({}) to {} ({}); \
+                 keep {} addresses by using the key in a {} descriptor",
+                from.script_type_name(),
+                from,
+                to.script_type_name(),
+                to,
+                from.script_type_name(),
+                descriptor_template(*from)
+            ),
+            ConversionWarning::NetworkChanged { from, to } => write!(
+                f,
+                "network changes from {} to {}; the key will derive {} addresses",
+                from, to, to
+            ),
+        }
+    }
+}
+
+/// Descriptor wrapping that keeps the script type implied by a version
+fn descriptor_template(version: Version) -> &'static str {
+    match version {
+        Version::Xpub | Version::Tpub => "pkh()",
+        Version::Ypub | Version::Upub => "sh(wpkh())",
+        Version::Zpub | Version::Vpub => "wpkh()",
+        Version::YpubMultisig | Version::UpubMultisig => "sh(wsh(sortedmulti()))",
+        Version::ZpubMultisig | Version::VpubMultisig => "wsh(sortedmulti())",
+    }
+}
@@ -0,0 +1,602 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::path::{format_step, is_hardened, parse_step};
+use crate::{DerivationPath, Error, IndexRange, Network, ScriptType, Xpub};
+
+/// Characters allowed in descriptors, ordered by their checksum symbol value (BIP380)
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+
+/// Characters of the 8 character descriptor checksum
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Length of the descriptor checksum
+const CHECKSUM_LEN: usize = 8;
+
+/// Generator of the descriptor checksum BCH code
+const GENERATOR: [u64; 5] = [
+    0xf5dee51989,
+    0xa9fdca3312,
+    0x1bab10e32d,
+    0x3706b1677a,
+    0x644d626ffd,
+];
+
+/// Feeds one symbol into the checksum polynomial
+fn polymod(c: u64, value: u64) -> u64 {
+    let c0 = c >> 35;
+    let mut c = ((c & 0x7_ffff_ffff) << 5) ^ value;
+    for (i, generator) in GENERATOR.iter().enumerate() {
+        if (c0 >> i) & 1 == 1 {
+            c ^= generator;
+        }
+    }
+    c
+}
+
+/// Computes the 8 character checksum of a descriptor without its "#checksum" suffix
+pub fn checksum(descriptor: &str) -> Result<String, Error> {
+    let mut c = 1u64;
+    let mut class = 0u64;
+    let mut class_count = 0;
+
+    for ch in descriptor.chars() {
+        let position = INPUT_CHARSET.find(ch).ok_or_else(|| {
+            Error::InvalidDescriptor(format!("invalid character '{}'", ch.escape_default()))
+        })? as u64;
+        // The low 5 bits are fed directly, the class of every 3 characters separately
+        c = polymod(c, position & 31);
+        class = class * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..CHECKSUM_LEN {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+
+    Ok((0..CHECKSUM_LEN)
+        .map(|i| CHECKSUM_CHARSET[((c >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+/// Formats path steps without the leading "m", as used inside descriptors
+fn format_steps(f: &mut fmt::Formatter<'_>, path: &DerivationPath) -> fmt::Result {
+    for step in path.steps() {
+        write!(f, "/{}", format_step(*step))?;
+    }
+    if path.has_wildcard() {
+        f.write_str("/*")?;
+    }
+    Ok(())
+}
+
+/// Origin of a key: the master key fingerprint and the path from the master key
+/// Written as "[d34db33f/84'/0'/0']" in front of a descriptor key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyOrigin {
+    pub fingerprint: u32,     // Fingerprint of the master key
+    pub path: DerivationPath, // Path from the master key to the key
+}
+
+impl KeyOrigin {
+    /// Creates the origin of a key at `path` below the master key with `fingerprint`
+    pub fn new(fingerprint: u32, path: DerivationPath) -> Self {
+        Self { fingerprint, path }
+    }
+
+    /// Origin of the master key with the given fingerprint: an empty path
+    pub fn master(fingerprint: u32) -> Self {
+        Self::new(fingerprint, DerivationPath::default())
+    }
+
+    /// Origin of the child at `index` of the key with this origin
+    pub fn child(&self, index: u32) -> Self {
+        Self::new(self.fingerprint, self.path.child(index))
+    }
+}
+
+impl fmt::Display for KeyOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08x}", self.fingerprint)?;
+        format_steps(f, &self.path)
+    }
+}
+
+impl FromStr for KeyOrigin {
+    type Err = Error;
+
+    /// Parses "fingerprint/path" as found between the brackets of a key expression
+    /// The brackets themselves are accepted as well, as in "[d34db33f/84'/0'/0']"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .unwrap_or(s);
+        let (fingerprint, path) = match s.split_once('/') {
+            Some((fingerprint, path)) => (fingerprint, path.parse()?),
+            None => (s, DerivationPath::default()),
+        };
+
+        if fingerprint.len() != 8 || !fingerprint.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::InvalidDescriptor(format!(
+                "key origin fingerprint {} must be 8 hex characters",
+                fingerprint
+            )));
+        }
+        if path.has_wildcard() {
+            return Err(Error::InvalidDescriptor(format!(
+                "key origin {} cannot contain a wildcard",
+                s
+            )));
+        }
+
+        Ok(Self {
+            fingerprint: u32::from_str_radix(fingerprint, 16).unwrap(),
+            path,
+        })
+    }
+}
+
+/// A BIP389 multipath step such as "<0;1>", standing for one branch per child number
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Multipath {
+    pub position: usize,   // Position of the step in the key's path
+    pub indices: Vec<u32>, // Child number of each branch, without duplicates
+}
+
+impl fmt::Display for Multipath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indices: Vec<String> = self.indices.iter().map(|i| format_step(*i)).collect();
+        write!(f, "<{}>", indices.join(";"))
+    }
+}
+
+impl FromStr for Multipath {
+    type Err = Error;
+
+    /// Parses the "<a;b;...>" tuple of a multipath step, at position 0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tuple = s
+            .strip_prefix('<')
+            .and_then(|tuple| tuple.strip_suffix('>'))
+            .ok_or_else(|| Error::InvalidDescriptor(format!("invalid multipath step {}", s)))?;
+
+        let mut indices = Vec::new();
+        for step in tuple.split(';') {
+            let index = parse_step(step)?;
+            if indices.contains(&index) {
+                return Err(Error::InvalidDescriptor(format!(
+                    "multipath step {} repeats index {}",
+                    s, step
+                )));
+            }
+            indices.push(index);
+        }
+        if indices.len() < 2 {
+            return Err(Error::InvalidDescriptor(format!(
+                "multipath step {} needs at least two indices",
+                s
+            )));
+        }
+
+        Ok(Self {
+            position: 0,
+            indices,
+        })
+    }
+}
+
+/// An extended public key expression of a descriptor, such as
+/// "[d34db33f/84'/0'/0']xpub.../0/*" or "xpub.../<0;1>/*"
+/// The path below the key may only contain non-hardened steps
+#[derive(Clone)]
+pub struct DescriptorKey {
+    pub origin: Option<KeyOrigin>,    // Optional origin of the extended key
+    pub xpub: Xpub,                   // Extended public key
+    pub path: DerivationPath,         // Path below the key, using the first multipath branch
+    pub multipath: Option<Multipath>, // Optional multipath step (BIP389)
+}
+
+impl DescriptorKey {
+    /// Returns true if the key ends with a wildcard
+    pub fn is_ranged(&self) -> bool {
+        self.path.has_wildcard()
+    }
+
+    /// Number of branches of the key: the length of its multipath tuple, or 1
+    pub fn branch_count(&self) -> usize {
+        self.multipath
+            .as_ref()
+            .map_or(1, |multipath| multipath.indices.len())
+    }
+
+    /// Expands a multipath key into one key per branch
+    /// Keys without a multipath step are returned as their only branch
+    pub fn branches(&self) -> Vec<DescriptorKey> {
+        let Some(multipath) = &self.multipath else {
+            return vec![self.clone()];
+        };
+
+        multipath
+            .indices
+            .iter()
+            .map(|index| {
+                let mut steps = self.path.steps().to_vec();
+                steps[multipath.position] = *index;
+                let mut path = DerivationPath::new(steps);
+                if self.path.has_wildcard() {
+                    path = path.with_wildcard();
+                }
+                DescriptorKey {
+                    origin: self.origin.clone(),
+                    xpub: self.xpub.clone(),
+                    path,
+                    multipath: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Full derivation path of the key: the origin path, when known, followed by the
+    /// path below the extended key, including its wildcard
+    /// Multipath keys use their first branch
+    pub fn full_path(&self) -> DerivationPath {
+        let mut steps = self
+            .origin
+            .as_ref()
+            .map_or_else(Vec::new, |origin| origin.path.steps().to_vec());
+        steps.extend_from_slice(self.path.steps());
+
+        let path = DerivationPath::new(steps);
+        if self.is_ranged() {
+            path.with_wildcard()
+        } else {
+            path
+        }
+    }
+
+    /// The extended key with the key origin attached, when the expression has one
+    /// Keys derived from it carry their full origin, e.g. for PSBT key paths
+    pub fn xpub_with_origin(&self) -> Xpub {
+        match &self.origin {
+            Some(origin) => self.xpub.clone().with_origin(origin.clone()),
+            None => self.xpub.clone(),
+        }
+    }
+
+    /// Derives the key at the path below the extended key, up to the wildcard
+    /// Multipath keys must be expanded into their branches first
+    pub fn derive_parent(&self) -> Result<Xpub, Error> {
+        if self.multipath.is_some() {
+            return Err(Error::InvalidDescriptor(format!(
+                "multipath key {} must be expanded into its branches",
+                self
+            )));
+        }
+        self.xpub_with_origin()
+            .derive_path(&DerivationPath::new(self.path.steps().to_vec()))
+    }
+
+    /// Derives the key for the given wildcard index
+    /// The index is ignored by keys without a wildcard
+    pub fn derive(&self, index: u32) -> Result<Xpub, Error> {
+        let parent = self.derive_parent()?;
+        if self.is_ranged() {
+            parent.derive_non_hardened(index)
+        } else {
+            Ok(parent)
+        }
+    }
+}
+
+impl fmt::Display for DescriptorKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(origin) = &self.origin {
+            write!(f, "[{}]", origin)?;
+        }
+        f.write_str(&self.xpub.to_base58())?;
+        for (position, step) in self.path.steps().iter().enumerate() {
+            match &self.multipath {
+                Some(multipath) if multipath.position == position => write!(f, "/{}", multipath)?,
+                _ => write!(f, "/{}", format_step(*step))?,
+            }
+        }
+        if self.path.has_wildcard() {
+            f.write_str("/*")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DescriptorKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (origin, key) = match s.strip_prefix('[') {
+            Some(rest) => {
+                let (origin, key) = rest.split_once(']').ok_or_else(|| {
+                    Error::InvalidDescriptor(format!("unterminated key origin in {}", s))
+                })?;
+                (Some(origin.parse()?), key)
+            }
+            None => (None, s),
+        };
+
+        let (key, path, multipath) = match key.split_once('/') {
+            Some((key, "")) => {
+                return Err(Error::InvalidDescriptor(format!(
+                    "empty derivation path after {}",
+                    key
+                )))
+            }
+            Some((key, path)) => {
+                let (path, multipath) = parse_key_path(path)?;
+                (key, path, multipath)
+            }
+            None => (key, DerivationPath::default(), None),
+        };
+
+        // Single keys are written as hex, which Base58 mostly accepts as well
+        if matches!(key.len(), 64 | 66) && key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::InvalidDescriptor(format!(
+                "key {} is not an extended public key",
+                key
+            )));
+        }
+        let xpub = Xpub::from_base58(key)?;
+        if let Some((_, step)) = path.first_hardened() {
+            return Err(Error::HardenedDerivation(step));
+        }
+        if let Some(step) = multipath
+            .iter()
+            .flat_map(|multipath| multipath.indices.iter())
+            .find(|index| is_hardened(**index))
+        {
+            return Err(Error::HardenedDerivation(*step));
+        }
+
+        Ok(Self {
+            origin,
+            xpub,
+            path,
+            multipath,
+        })
+    }
+}
+
+/// Parses the path below a descriptor key, which may contain one multipath step
+/// The returned path holds the first branch's child number at the multipath step
+fn parse_key_path(s: &str) -> Result<(DerivationPath, Option<Multipath>), Error> {
+    let mut steps: Vec<String> = s.split('/').map(str::to_string).collect();
+    let mut multipath: Option<Multipath> = None;
+
+    for (position, step) in steps.iter_mut().enumerate() {
+        if !step.starts_with('<') {
+            continue;
+        }
+        if multipath.is_some() {
+            return Err(Error::InvalidDescriptor(format!(
+                "only one multipath step is allowed in {}",
+                s
+            )));
+        }
+        let parsed = Multipath {
+            position,
+            ..step.parse()?
+        };
+        *step = format_step(parsed.indices[0]);
+        multipath = Some(parsed);
+    }
+
+    Ok((steps.join("/").parse()?, multipath))
+}
+
+/// A single key output descriptor (BIP380-386)
+#[derive(Clone)]
+pub enum Descriptor {
+    /// pkh(KEY): legacy pay to public key hash (BIP381)
+    Pkh(DescriptorKey),
+    /// sh(wpkh(KEY)): P2WPKH nested in pay to script hash (BIP381, BIP382)
+    ShWpkh(DescriptorKey),
+    /// wpkh(KEY): native segwit v0 pay to witness public key hash (BIP382)
+    Wpkh(DescriptorKey),
+    /// tr(KEY): Taproot key path output without a script tree (BIP386)
+    Tr(DescriptorKey),
+}
+
+impl Descriptor {
+    /// Key expression of the descriptor
+    pub fn key(&self) -> &DescriptorKey {
+        match self {
+            Descriptor::Pkh(key)
+            | Descriptor::ShWpkh(key)
+            | Descriptor::Wpkh(key)
+            | Descriptor::Tr(key) => key,
+        }
+    }
+
+    /// Mutable key expression of the descriptor
+    fn key_mut(&mut self) -> &mut DescriptorKey {
+        match self {
+            Descriptor::Pkh(key)
+            | Descriptor::ShWpkh(key)
+            | Descriptor::Wpkh(key)
+            | Descriptor::Tr(key) => key,
+        }
+    }
+
+    /// Script type of the addresses described by the descriptor
+    pub fn script_type(&self) -> ScriptType {
+        match self {
+            Descriptor::Pkh(_) => ScriptType::P2pkh,
+            Descriptor::ShWpkh(_) => ScriptType::P2shP2wpkh,
+            Descriptor::Wpkh(_) => ScriptType::P2wpkh,
+            Descriptor::Tr(_) => ScriptType::P2tr,
+        }
+    }
+
+    /// Network of the generated addresses, implied by the key's version bytes
+    pub fn network(&self) -> Network {
+        self.key().xpub.network
+    }
+
+    /// Returns a copy of this descriptor generating addresses for the given network
+    /// Only networks matching the key's version bytes are accepted
+    pub fn with_network(mut self, network: Network) -> Result<Self, Error> {
+        let key = self.key_mut();
+        key.xpub = key.xpub.clone().with_network(network)?;
+        Ok(self)
+    }
+
+    /// Returns true if the descriptor ends with a wildcard and describes many addresses
+    pub fn is_ranged(&self) -> bool {
+        self.key().is_ranged()
+    }
+
+    /// Returns true if the descriptor contains a multipath step (BIP389)
+    pub fn is_multipath(&self) -> bool {
+        self.key().multipath.is_some()
+    }
+
+    /// Number of branches of the descriptor, 1 without a multipath step
+    pub fn branch_count(&self) -> usize {
+        self.key().branch_count()
+    }
+
+    /// Expands a multipath descriptor into one descriptor per branch
+    /// Descriptors without a multipath step are returned as their only branch
+    pub fn branches(&self) -> Vec<Descriptor> {
+        self.key()
+            .branches()
+            .into_iter()
+            .map(|key| {
+                let mut descriptor = self.clone();
+                *descriptor.key_mut() = key;
+                descriptor
+            })
+            .collect()
+    }
+
+    /// Checksum of the descriptor as displayed after "#"
+    pub fn checksum(&self) -> String {
+        checksum(&self.body()).expect("formatted descriptors only use checksum characters")
+    }
+
+    /// Descriptor string without the checksum
+    fn body(&self) -> String {
+        match self {
+            Descriptor::Pkh(key) => format!("pkh({})", key),
+            Descriptor::ShWpkh(key) => format!("sh(wpkh({}))", key),
+            Descriptor::Wpkh(key) => format!("wpkh({})", key),
+            Descriptor::Tr(key) => format!("tr({})", key),
+        }
+    }
+
+    /// Generates the address for the given wildcard index
+    /// The index is ignored by descriptors without a wildcard
+    pub fn address(&self, index: u32) -> Result<String, Error> {
+        Ok(self.key().derive(index)?.to_address(self.script_type()))
+    }
+
+    /// Generates the addresses for a range of wildcard indices
+    /// Each address is returned with its index
+    pub fn address_range(&self, range: IndexRange) -> Result<Vec<(u32, String)>, Error> {
+        if !self.is_ranged() {
+            return Err(Error::InvalidDescriptor(
+                "a range requires a descriptor ending with a wildcard".to_string(),
+            ));
+        }
+        let parent = self.key().derive_parent()?;
+        parent.derive_bip32_address_range(range, self.script_type())
+    }
+
+    /// Generates the addresses for a range of wildcard indices on every branch
+    /// Each address is labeled with its branch and index; all addresses of the first
+    /// branch are returned before those of the next
+    pub fn branch_address_range(
+        &self,
+        range: IndexRange,
+    ) -> Result<Vec<(usize, u32, String)>, Error> {
+        let mut addresses = Vec::with_capacity(range.len() * self.branch_count());
+
+        for (branch, descriptor) in self.branches().iter().enumerate() {
+            let branch_addresses = descriptor.address_range(range)?;
+            addresses.extend(
+                branch_addresses
+                    .into_iter()
+                    .map(|(i, address)| (branch, i, address)),
+            );
+        }
+        Ok(addresses)
+    }
+}
+
+impl fmt::Display for Descriptor {
+    /// Formats the descriptor followed by "#" and its checksum
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let body = self.body();
+        let checksum = checksum(&body).expect("formatted descriptors only use checksum characters");
+        write!(f, "{}#{}", body, checksum)
+    }
+}
+
+impl FromStr for Descriptor {
+    type Err = Error;
+
+    /// Parses a descriptor, verifying its checksum when one is present
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let body = match s.rsplit_once('#') {
+            Some((body, found)) => {
+                let expected = checksum(body)?;
+                if found != expected {
+                    return Err(Error::DescriptorChecksumMismatch {
+                        expected,
+                        found: found.to_string(),
+                    });
+                }
+                body
+            }
+            None => {
+                checksum(s)?;
+                s
+            }
+        };
+
+        if let Some(inner) = strip_call(body, "pkh") {
+            Ok(Descriptor::Pkh(inner.parse()?))
+        } else if let Some(inner) = strip_call(body, "wpkh") {
+            Ok(Descriptor::Wpkh(inner.parse()?))
+        } else if let Some(inner) = strip_call(body, "sh") {
+            let inner = strip_call(inner, "wpkh").ok_or_else(|| {
+                Error::InvalidDescriptor(format!("unsupported sh() descriptor {}", inner))
+            })?;
+            Ok(Descriptor::ShWpkh(inner.parse()?))
+        } else if let Some(inner) = strip_call(body, "tr") {
+            if inner.contains(',') {
+                return Err(Error::InvalidDescriptor(
+                    "tr() script trees are not supported".to_string(),
+                ));
+            }
+            Ok(Descriptor::Tr(inner.parse()?))
+        } else {
+            Err(Error::InvalidDescriptor(format!(
+                "unsupported descriptor {} (expected pkh, sh(wpkh), wpkh or tr)",
+                body
+            )))
+        }
+    }
+}
+
+/// Returns the arguments of "name(...)" if the string is a call to `name`
+fn strip_call<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    s.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')
+}
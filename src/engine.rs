@@ -0,0 +1,203 @@
+use std::sync::OnceLock;
+
+use hmac::{Hmac, Mac};
+use secp256k1::XOnlyPublicKey;
+use secp256k1::{All, Context, Scalar, Secp256k1, SecretKey, Verification, VerifyOnly};
+use sha2::Sha512;
+
+use crate::{bech32, hash, path};
+use crate::{AccountAddressIter, AddressIter, ChainSelection, DerivationPath, Error};
+use crate::{IndexRange, KeyOrigin, ScriptType, Xpub};
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Derives child keys and addresses with one reusable secp256k1 context
+/// Creating a context allocates and initializes it, so bulk derivation shares one
+/// engine instead of building a context for every key. Public derivation only needs
+/// a verification context; `Engine::new` builds a full context that can also sign
+pub struct Engine<C: Context = All> {
+    secp: Secp256k1<C>, // Context used for every curve operation
+}
+
+impl Engine<All> {
+    /// Creates an engine with a full (signing and verification) context
+    pub fn new() -> Self {
+        Self::from_context(Secp256k1::new())
+    }
+}
+
+impl Default for Engine<All> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Engine<VerifyOnly> {
+    /// Creates an engine with a verification-only context, enough for public derivation
+    pub fn verification_only() -> Self {
+        Self::from_context(Secp256k1::verification_only())
+    }
+}
+
+impl<C: Context> Engine<C> {
+    /// Creates an engine around an existing context
+    pub fn from_context(secp: Secp256k1<C>) -> Self {
+        Self { secp }
+    }
+
+    /// The secp256k1 context of this engine
+    pub fn context(&self) -> &Secp256k1<C> {
+        &self.secp
+    }
+}
+
+impl<C: Verification> Engine<C> {
+    /// Derives a non-hardened child Xpub from the given Xpub
+    /// Returns `Error::InvalidTweak` for the rare indices BIP32 says to skip
+    pub fn derive_non_hardened(&self, xpub: &Xpub, index: u32) -> Result<Xpub, Error> {
+        self.derive_child(xpub, xpub.fingerprint(), index)
+    }
+
+    /// Derives a non-hardened child of `xpub` whose fingerprint is already known
+    /// Bulk derivation computes the parent fingerprint once instead of once per child
+    pub(crate) fn derive_child(
+        &self,
+        xpub: &Xpub,
+        fingerprint: u32,
+        index: u32,
+    ) -> Result<Xpub, Error> {
+        // Verify index is not hardened
+        if path::is_hardened(index) {
+            return Err(Error::HardenedDerivation(index)); // Hardened keys are not allowed for Xpub
+        }
+        let depth = xpub.depth.checked_add(1).ok_or(Error::DepthOverflow)?;
+
+        // Prepare data for HMAC-SHA512
+        // parent_pubkey (33 bytes) || child_index (4 bytes)
+        let mut data = [0u8; 37];
+        data[..33].copy_from_slice(&xpub.public_key.serialize());
+        data[33..].copy_from_slice(&index.to_be_bytes());
+
+        // Generate child key material using HMAC-SHA512
+        let mut mac =
+            HmacSha512::new_from_slice(&xpub.chain_code).expect("HMAC can take a key of any size");
+        mac.update(&data);
+        let result = mac.finalize().into_bytes();
+
+        // Split the result into two 32-byte halves
+        let (i_l, i_r) = result.split_at(32);
+
+        // Compute the child public key
+        // I_L >= n or a child at the point at infinity make the index invalid
+        let tweak = SecretKey::from_slice(i_l).map_err(|_| Error::InvalidTweak(index))?;
+        let child_pubkey = xpub
+            .public_key
+            .add_exp_tweak(&self.secp, &tweak.into())
+            .map_err(|_| Error::InvalidTweak(index))?;
+
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(i_r);
+
+        // Create the child Xpub
+        Ok(Xpub {
+            version: xpub.version,
+            network: xpub.network,
+            depth,
+            parent_fingerprint: fingerprint,
+            child_number: index,
+            chain_code,
+            public_key: child_pubkey,
+            origin: child_origin(xpub.origin.as_ref(), xpub.depth, fingerprint, index),
+        })
+    }
+
+    /// Derives the key at the given path, relative to the given Xpub
+    /// Only non-hardened steps can be derived from a public key
+    pub fn derive_path(&self, xpub: &Xpub, path: &DerivationPath) -> Result<Xpub, Error> {
+        if path.has_wildcard() {
+            return Err(Error::InvalidPath(format!(
+                "cannot derive a single key from wildcard path {}",
+                path
+            )));
+        }
+        if let Some((_, step)) = path.first_hardened() {
+            return Err(Error::HardenedDerivation(step));
+        }
+
+        let mut current = xpub.clone();
+        for step in path.steps() {
+            current = self.derive_non_hardened(&current, *step)?;
+        }
+        Ok(current)
+    }
+
+    /// Computes the BIP86 Taproot output key: internal key tweaked with hash_TapTweak(P)
+    pub fn taproot_output_key(&self, xpub: &Xpub) -> XOnlyPublicKey {
+        let (internal_key, _parity) = xpub.public_key.x_only_public_key();
+
+        let tweak = hash::tagged_hash("TapTweak", &internal_key.serialize());
+        let tweak = Scalar::from_be_bytes(tweak)
+            .expect("TapTweak hash is below the curve order with overwhelming probability");
+        let (output_key, _parity) = internal_key
+            .add_tweak(&self.secp, &tweak)
+            .expect("TapTweak of a valid key does not produce the point at infinity");
+
+        output_key
+    }
+
+    /// Generates an address of the requested script type from the Xpub's public key
+    pub fn address(&self, xpub: &Xpub, script_type: ScriptType) -> String {
+        match script_type {
+            ScriptType::P2tr => {
+                let output_key = self.taproot_output_key(xpub);
+                bech32::encode_segwit_address(xpub.network.bech32_hrp(), 1, &output_key.serialize())
+                    .expect("32 byte witness v1 program is always valid")
+            }
+            _ => xpub.to_address(script_type),
+        }
+    }
+
+    /// Lazily derives the children of `xpub` in the range with this engine
+    /// Yields (index, child Xpub, address), skipping indices BIP32 says to skip
+    pub fn address_iter(
+        &self,
+        xpub: &Xpub,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<AddressIter<'_, C>, Error> {
+        AddressIter::new(self, xpub.clone(), range, script_type)
+    }
+
+    /// Lazily derives addresses on the selected chains of a BIP44 account with this engine
+    /// Yields (chain, index, child Xpub, address), receive addresses before change addresses
+    pub fn bip44_address_iter(
+        &self,
+        account: &Xpub,
+        selection: ChainSelection,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<AccountAddressIter<'_, C>, Error> {
+        AccountAddressIter::new(self, account, selection, range, script_type)
+    }
+}
+
+/// Shared engine used by the `Xpub` and `Xprv` methods, created on first use
+pub fn global() -> &'static Engine {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    ENGINE.get_or_init(Engine::new)
+}
+
+/// Origin of the child at `index` of a parent key with the given origin, depth and
+/// fingerprint; a master key (depth 0) is the origin of its children
+pub(crate) fn child_origin(
+    origin: Option<&KeyOrigin>,
+    depth: u8,
+    fingerprint: u32,
+    index: u32,
+) -> Option<KeyOrigin> {
+    match origin {
+        Some(origin) => Some(origin.child(index)),
+        None if depth == 0 => Some(KeyOrigin::master(fingerprint).child(index)),
+        None => None,
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt;
+
+use base58::FromBase58Error;
+
+use crate::bech32::Variant;
+use crate::hash;
+use crate::path::{format_step, HARDENED_OFFSET};
+use crate::{AddressType, Network, Version};
+
+/// Errors returned by key parsing, derivation and address generation
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The string is not valid Base58
+    Base58(FromBase58Error),
+    /// Decoded data does not have the expected length
+    InvalidLength { expected: usize, found: usize },
+    /// The Base58Check checksum does not match the payload
+    ChecksumMismatch { expected: [u8; 4], found: [u8; 4] },
+    /// The version bytes are not a known SLIP-132 public key version
+    UnknownVersion([u8; 4]),
+    /// Unrecognized SLIP-132 key prefix name (e.g. "xpub", "Zpub")
+    UnknownVersionPrefix(String),
+    /// The version bytes belong to an extended private key (e.g. "xprv")
+    PrivateKeyVersion(&'static str),
+    /// The version bytes belong to an extended public key (e.g. "xpub")
+    PublicKeyVersion(&'static str),
+    /// The serialized public key is not a valid secp256k1 point
+    InvalidPublicKey(secp256k1::Error),
+    /// The private key is zero, not below the curve order, or wrongly serialized
+    InvalidPrivateKey(secp256k1::Error),
+    /// A depth 0 key has a non-zero parent fingerprint or child number
+    InvalidMasterKey,
+    /// A hardened child was requested from an extended public key
+    HardenedDerivation(u32),
+    /// The child would be deeper than the 255 levels the serialization allows
+    DepthOverflow,
+    /// The child index produces an invalid key and must be skipped (BIP32)
+    InvalidTweak(u32),
+    /// A multisig version (e.g. "Zpub") was used where a single key script type is implied
+    MultisigVersion(Version),
+    /// The network does not belong to the network class of the version bytes
+    NetworkMismatch { network: Network, version: Version },
+    /// A derivation path could not be parsed or used
+    InvalidPath(String),
+    /// An index range starts after its end
+    RangeStartAfterEnd { start: u32, end: u32 },
+    /// An index range ends above the hardened boundary at 2^31
+    RangeCrossesHardened { end: u32 },
+    /// The end of a range given by its start and count does not fit in a u32
+    RangeOverflow { start: u32, count: u32 },
+    /// An index range step of zero
+    ZeroRangeStep,
+    /// A bech32 string is longer than 90 characters
+    Bech32TooLong(usize),
+    /// A bech32 string contains a character outside its charset
+    Bech32InvalidCharacter(char),
+    /// A bech32 string mixes upper and lower case
+    Bech32MixedCase,
+    /// A bech32 string has no "1" separator
+    Bech32MissingSeparator,
+    /// A bech32 human readable part is empty or has characters outside 33 to 126
+    Bech32InvalidHrp(String),
+    /// A bech32 checksum is missing or does not match
+    Bech32Checksum,
+    /// Bech32 data values are out of range or have non-zero padding bits
+    Bech32InvalidData,
+    /// A segwit address uses the human readable part of another network
+    WrongHrp { expected: String, found: String },
+    /// A segwit witness version above 16
+    InvalidWitnessVersion(u8),
+    /// A witness program is not 2 to 40 bytes long, or 20 or 32 bytes for version 0
+    InvalidWitnessProgramLength { version: u8, length: usize },
+    /// A segwit address uses the checksum variant of another witness version (BIP350)
+    WitnessVariantMismatch {
+        version: u8,
+        expected: Variant,
+        found: Variant,
+    },
+    /// Unrecognized network name
+    UnknownNetwork(String),
+    /// Unrecognized script type name
+    UnknownScriptType(String),
+    /// Unrecognized chain name
+    UnknownChain(String),
+    /// Unrecognized account purpose
+    UnknownPurpose(String),
+    /// Mnemonic entropy is not 16, 20, 24, 28 or 32 bytes long
+    InvalidEntropyLength(usize),
+    /// Mnemonic does not have 12, 15, 18, 21 or 24 words
+    InvalidWordCount(usize),
+    /// The mnemonic word at this position (starting at 1) is not in the wordlist
+    UnknownWord(usize),
+    /// The mnemonic checksum bits do not match its entropy
+    InvalidMnemonicChecksum,
+    /// An output descriptor could not be parsed or used
+    InvalidDescriptor(String),
+    /// The descriptor checksum does not match the descriptor (BIP380)
+    DescriptorChecksumMismatch { expected: String, found: String },
+    /// A multisig policy has an invalid threshold, key count or script size
+    InvalidMultisig(String),
+    /// Unrecognized multisig address type name
+    UnknownMultisigType(String),
+    /// A Base58 address has a version byte no supported network uses
+    UnknownAddressVersion(u8),
+    /// An address is valid on another network than the requested one
+    AddressNetworkMismatch { address: Network, network: Network },
+    /// The address pays to an output that is not a supported single key type
+    UnsupportedAddressType(AddressType),
+    /// Unrecognized output format name
+    UnknownFormat(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Base58(FromBase58Error::InvalidBase58Character(character, position)) => write!(
+                f,
+                "Base58 decode error: invalid character '{}' at position {}",
+                character, position
+            ),
+            Error::Base58(FromBase58Error::InvalidBase58Length) => {
+                f.write_str("Base58 decode error: invalid length")
+            }
+            Error::InvalidLength { expected, found } => write!(
+                f,
+                "Invalid length: expected {} bytes, found {}",
+                expected, found
+            ),
+            Error::ChecksumMismatch { expected, found } => write!(
+                f,
+                "Checksum mismatch: expected {}, found {}",
+                hash::hex(expected),
+                hash::hex(found)
+            ),
+            Error::UnknownVersion(bytes) => write!(
+                f,
+                "Unknown version bytes: 0x{:08X}",
+                u32::from_be_bytes(*bytes)
+            ),
+            Error::UnknownVersionPrefix(name) => write!(
+                f,
+                "Unknown key version: {} (expected xpub, ypub, Ypub, zpub, Zpub, tpub, upub, Upub, vpub or Vpub)",
+                name
+            ),
+            Error::PrivateKeyVersion(prefix) => write!(
+                f,
+                "Private key version ({}) is not allowed, expected an extended public key",
+                prefix
+            ),
+            Error::PublicKeyVersion(prefix) => write!(
+                f,
+                "Public key version ({}) is not allowed, expected an extended private key",
+                prefix
+            ),
+            Error::InvalidPublicKey(e) => write!(f, "Invalid public key: {}", e),
+            Error::InvalidPrivateKey(e) => write!(f, "Invalid private key: {}", e),
+            Error::InvalidMasterKey => f.write_str(
+                "Master key (depth 0) must have a zero parent fingerprint and child number",
+            ),
+            Error::HardenedDerivation(index) => write!(
+                f,
+                "Cannot derive hardened child {} from an extended public key",
+                format_step(*index)
+            ),
+            Error::DepthOverflow => f.write_str("Maximum derivation depth of 255 exceeded"),
+            Error::InvalidTweak(index) => write!(
+                f,
+                "Child {} produces an invalid key and must be skipped",
+                index
+            ),
+            Error::MultisigVersion(version) => write!(
+                f,
+                "{} is a multisig key version ({} multisig); choose a single key script type \
+                 explicitly or use the key in a multisig policy",
+                version,
+                version.script_type_name()
+            ),
+            Error::NetworkMismatch { network, version } => write!(
+                f,
+                "Network {} does not match {} version bytes",
+                network, version
+            ),
+            Error::InvalidPath(msg) => write!(f, "Invalid derivation path: {}", msg),
+            Error::RangeStartAfterEnd { start, end } => write!(
+                f,
+                "Invalid index range: start {} is greater than end {}",
+                start, end
+            ),
+            Error::RangeCrossesHardened { end } => write!(
+                f,
+                "Invalid index range: end {} crosses the hardened index boundary 2^31 ({})",
+                end, HARDENED_OFFSET
+            ),
+            Error::RangeOverflow { start, count } => write!(
+                f,
+                "Invalid index range: {} indices starting at {} overflow the index space",
+                count, start
+            ),
+            Error::ZeroRangeStep => f.write_str("Invalid index range: step must be at least 1"),
+            Error::Bech32TooLong(len) => write!(
+                f,
+                "Bech32 error: string of {} characters exceeds 90 characters",
+                len
+            ),
+            Error::Bech32InvalidCharacter(c) => {
+                write!(f, "Bech32 error: invalid character {:?}", c)
+            }
+            Error::Bech32MixedCase => f.write_str("Bech32 error: mixed case bech32 string"),
+            Error::Bech32MissingSeparator => f.write_str("Bech32 error: missing bech32 separator"),
+            Error::Bech32InvalidHrp(hrp) => {
+                write!(f, "Bech32 error: invalid human readable part {:?}", hrp)
+            }
+            Error::Bech32Checksum => f.write_str("Bech32 error: invalid bech32 checksum"),
+            Error::Bech32InvalidData => {
+                f.write_str("Bech32 error: data values out of range or invalid padding")
+            }
+            Error::WrongHrp { expected, found } => write!(
+                f,
+                "Bech32 error: unexpected human readable part: expected {}, found {}",
+                expected, found
+            ),
+            Error::InvalidWitnessVersion(version) => {
+                write!(f, "Invalid witness version: {}", version)
+            }
+            Error::InvalidWitnessProgramLength { version, length } => write!(
+                f,
+                "Invalid witness v{} program length: {} bytes",
+                version, length
+            ),
+            Error::WitnessVariantMismatch {
+                version,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Witness version {} must use {:?} checksum, found {:?}",
+                version, expected, found
+            ),
+            Error::UnknownNetwork(name) => write!(
+                f,
+                "Unknown network: {} (expected bitcoin, testnet, testnet4, signet or regtest)",
+                name
+            ),
+            Error::UnknownScriptType(name) => write!(
+                f,
+                "Unknown script type: {} (expected p2pkh, p2sh-p2wpkh, p2wpkh or p2tr)",
+                name
+            ),
+            Error::UnknownChain(name) => write!(
+                f,
+                "Unknown chain: {} (expected external, internal or both)",
+                name
+            ),
+            Error::UnknownPurpose(name) => {
+                write!(f, "Unknown purpose: {} (expected 44, 49, 84 or 86)", name)
+            }
+            Error::InvalidEntropyLength(len) => write!(
+                f,
+                "Invalid entropy length: {} bytes (expected 16, 20, 24, 28 or 32)",
+                len
+            ),
+            Error::InvalidWordCount(count) => write!(
+                f,
+                "Invalid mnemonic word count: {} (expected 12, 15, 18, 21 or 24)",
+                count
+            ),
+            Error::UnknownWord(position) => write!(
+                f,
+                "Mnemonic word {} is not in the BIP39 English wordlist",
+                position
+            ),
+            Error::InvalidMnemonicChecksum => f.write_str("Invalid mnemonic checksum"),
+            Error::InvalidDescriptor(msg) => write!(f, "Invalid descriptor: {}", msg),
+            Error::DescriptorChecksumMismatch { expected, found } => write!(
+                f,
+                "Descriptor checksum mismatch: expected {}, found {}",
+                expected, found
+            ),
+            Error::InvalidMultisig(msg) => write!(f, "Invalid multisig: {}", msg),
+            Error::UnknownMultisigType(name) => write!(
+                f,
+                "Unknown multisig type: {} (expected p2sh, p2sh-p2wsh or p2wsh)",
+                name
+            ),
+            Error::UnknownAddressVersion(prefix) => write!(
+                f,
+                "Invalid address: unknown version byte 0x{:02x}",
+                prefix
+            ),
+            Error::AddressNetworkMismatch { address, network } => write!(
+                f,
+                "Invalid address: {} address is not valid on {}",
+                address, network
+            ),
+            Error::UnsupportedAddressType(address_type) => write!(
+                f,
+                "Invalid address: {} address is not a single key output",
+                address_type
+            ),
+            Error::UnknownFormat(name) => write!(
+                f,
+                "Unknown output format: {} (expected text, json, csv or ndjson)",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidPublicKey(e) | Error::InvalidPrivateKey(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<FromBase58Error> for Error {
+    fn from(e: FromBase58Error) -> Self {
+        Error::Base58(e)
+    }
+}
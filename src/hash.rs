@@ -0,0 +1,58 @@
+use hmac::{Hmac, Mac};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroize;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Computes SHA256(SHA256(data)), used for Base58Check checksums
+pub(crate) fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+/// Computes RIPEMD160(SHA256(data)), used for public key and script hashes
+pub(crate) fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// Computes a BIP340 tagged hash: SHA256(SHA256(tag) || SHA256(tag) || data)
+pub(crate) fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Formats bytes as lowercase hex
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Derives a 64 byte key with PBKDF2-HMAC-SHA512 (RFC 8018), as used for BIP39 seeds
+/// A single output block is computed, since the key length equals the HMAC output length
+pub(crate) fn pbkdf2_hmac_sha512(password: &[u8], salt: &[u8], rounds: u32) -> [u8; 64] {
+    let prf = HmacSha512::new_from_slice(password).expect("HMAC can take a key of any size");
+
+    // U_1 = PRF(password, salt || INT(1))
+    let mut mac = prf.clone();
+    mac.update(salt);
+    mac.update(&1u32.to_be_bytes());
+    let mut block = mac.finalize().into_bytes();
+
+    // T_1 = U_1 ^ U_2 ^ ... ^ U_rounds
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&block);
+    for _ in 1..rounds {
+        let mut mac = prf.clone();
+        mac.update(&block);
+        block = mac.finalize().into_bytes();
+        for (out, byte) in output.iter_mut().zip(block.iter()) {
+            *out ^= byte;
+        }
+    }
+    block.as_mut_slice().zeroize();
+
+    output
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+use base58::FromBase58;
+use secp256k1::PublicKey;
+
+use crate::hash;
+use crate::path::{format_step, is_hardened};
+use crate::{base58check, Error, MultisigType, Network, Record, ScriptType, Version, Xpub};
+
+/// Fields of a serialized extended public key, decoded for display
+/// Unlike `Xpub::from_base58`, a key with a wrong checksum is still decoded and
+/// reported with `checksum_valid` set to false
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyInfo {
+    pub version: Version,        // SLIP-132 version of the serialized key
+    pub network: Network,        // Network implied by the version bytes
+    pub script_type: ScriptType, // Single key script type implied by the version bytes
+    pub multisig_type: Option<MultisigType>, // Multisig script type of the multisig versions
+    pub depth: u8,               // Depth in the HD tree
+    pub parent_fingerprint: u32, // Fingerprint of the parent key
+    pub child_number: u32,       // Index of this key, including the hardened bit
+    pub chain_code: [u8; 32],    // Chain code (32 bytes)
+    pub public_key: PublicKey,   // Compressed public key (33 bytes)
+    pub fingerprint: u32,        // Fingerprint of this key
+    pub checksum_valid: bool,    // Whether the Base58Check checksum matches
+}
+
+impl KeyInfo {
+    /// Decodes a Base58 extended public key without rejecting a bad checksum
+    /// Fails when the key is not 82 bytes long, has unknown or private version
+    /// bytes, or holds an invalid public key
+    pub fn from_base58(xpub: &str) -> Result<Self, Error> {
+        let decoded = xpub.from_base58()?;
+
+        if decoded.len() != 82 {
+            return Err(Error::InvalidLength {
+                expected: 82,
+                found: decoded.len(),
+            });
+        }
+
+        let checksum_valid = base58check::verify_checksum(&decoded).is_ok();
+        let xpub = Xpub::from_payload(&decoded[..78])?;
+
+        Ok(Self {
+            checksum_valid,
+            ..Self::from_xpub(&xpub)
+        })
+    }
+
+    /// Describes an already parsed key, whose checksum is valid by construction
+    pub fn from_xpub(xpub: &Xpub) -> Self {
+        Self {
+            version: xpub.version,
+            network: xpub.network,
+            script_type: xpub.version.script_type(),
+            multisig_type: xpub.version.multisig_type(),
+            depth: xpub.depth,
+            parent_fingerprint: xpub.parent_fingerprint,
+            child_number: xpub.child_number,
+            chain_code: xpub.chain_code,
+            public_key: xpub.public_key,
+            fingerprint: xpub.fingerprint(),
+            checksum_valid: true,
+        }
+    }
+
+    /// Returns true when the key is a hardened child
+    pub fn is_hardened(&self) -> bool {
+        is_hardened(self.child_number)
+    }
+
+    /// Name of the implied script type, the multisig one for multisig versions
+    pub fn script_type_name(&self) -> &'static str {
+        self.version.script_type_name()
+    }
+
+    /// Fields as an output record, with keys and fingerprints in hex
+    pub fn to_record(&self) -> Record {
+        Record::new()
+            .with("version", self.version.prefix())
+            .with("version_bytes", hash::hex(&self.version.to_bytes()))
+            .with("network", self.network.name())
+            .with("script_type", self.script_type_name())
+            .with("multisig", self.multisig_type.is_some())
+            .with("depth", self.depth)
+            .with(
+                "parent_fingerprint",
+                format!("{:08x}", self.parent_fingerprint),
+            )
+            .with("child_number", self.child_number)
+            .with("hardened", self.is_hardened())
+            .with("chain_code", hash::hex(&self.chain_code))
+            .with("public_key", hash::hex(&self.public_key.serialize()))
+            .with("fingerprint", format!("{:08x}", self.fingerprint))
+            .with("checksum_valid", self.checksum_valid)
+    }
+}
+
+impl fmt::Display for KeyInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Version:            {} (0x{:08X})",
+            self.version,
+            u32::from_be_bytes(self.version.to_bytes())
+        )?;
+        if self.version.is_mainnet() {
+            writeln!(f, "Network:            {}", self.network)?;
+        } else {
+            writeln!(
+                f,
+                "Network:            {} (test versions are shared by testnet, testnet4, signet and regtest)",
+                self.network
+            )?;
+        }
+        match self.multisig_type {
+            Some(multisig_type) => writeln!(f, "Script type:        {} multisig", multisig_type)?,
+            None => writeln!(f, "Script type:        {}", self.script_type)?,
+        }
+        match self.depth {
+            0 => writeln!(f, "Depth:              0 (master key)")?,
+            depth => writeln!(f, "Depth:              {}", depth)?,
+        }
+        writeln!(f, "Parent fingerprint: {:08x}", self.parent_fingerprint)?;
+        if self.is_hardened() {
+            writeln!(
+                f,
+                "Child number:       {} ({}, hardened)",
+                self.child_number,
+                format_step(self.child_number)
+            )?;
+        } else {
+            writeln!(f, "Child number:       {}", self.child_number)?;
+        }
+        writeln!(f, "Chain code:         {}", hash::hex(&self.chain_code))?;
+        writeln!(
+            f,
+            "Public key:         {}",
+            hash::hex(&self.public_key.serialize())
+        )?;
+        writeln!(f, "Fingerprint:        {:08x}", self.fingerprint)?;
+        write!(
+            f,
+            "Checksum:           {}",
+            if self.checksum_valid {
+                "valid"
+            } else {
+                "INVALID"
+            }
+        )
+    }
+}
@@ -0,0 +1,146 @@
+use std::iter::{FusedIterator, StepBy};
+use std::ops::Range;
+
+use secp256k1::{All, Verification};
+
+use crate::engine::Engine;
+use crate::{Chain, ChainSelection, Error, IndexRange, ScriptType, Xpub};
+
+/// Lazily derives the children of an Xpub over an index range with their addresses
+/// Yields (index, child Xpub, address); indices that produce an invalid key are
+/// skipped as BIP32 requires, so their index is simply missing from the output
+pub struct AddressIter<'e, C: Verification = All> {
+    engine: &'e Engine<C>,       // Engine holding the secp256k1 context
+    parent: Xpub,                // Key the children are derived from
+    fingerprint: u32,            // Fingerprint of the parent, shared by all children
+    indices: StepBy<Range<u32>>, // Child indices still to derive
+    script_type: ScriptType,     // Script type of the generated addresses
+}
+
+impl<'e, C: Verification> AddressIter<'e, C> {
+    /// Creates an iterator over the children of `parent` in the range
+    /// Fails if the children would exceed the maximum depth of 255
+    pub fn new(
+        engine: &'e Engine<C>,
+        parent: Xpub,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Self, Error> {
+        // Checked once here, so the only derivation error left is an invalid tweak
+        parent.depth.checked_add(1).ok_or(Error::DepthOverflow)?;
+        Ok(Self {
+            engine,
+            fingerprint: parent.fingerprint(),
+            parent,
+            indices: (range.start()..range.end()).step_by(range.step() as usize),
+            script_type,
+        })
+    }
+}
+
+impl<C: Verification> Clone for AddressIter<'_, C> {
+    fn clone(&self) -> Self {
+        Self {
+            engine: self.engine,
+            parent: self.parent.clone(),
+            fingerprint: self.fingerprint,
+            indices: self.indices.clone(),
+            script_type: self.script_type,
+        }
+    }
+}
+
+impl<C: Verification> Iterator for AddressIter<'_, C> {
+    type Item = (u32, Xpub, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for index in self.indices.by_ref() {
+            // Indices BIP32 says to skip have no key and no address
+            if let Ok(child) = self
+                .engine
+                .derive_child(&self.parent, self.fingerprint, index)
+            {
+                let address = self.engine.address(&child, self.script_type);
+                return Some((index, child, address));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.indices.size_hint().1)
+    }
+}
+
+impl<C: Verification> FusedIterator for AddressIter<'_, C> {}
+
+/// Lazily derives addresses on the selected chains of a BIP44 style account
+/// Yields (chain, index, child Xpub, address) following account/chain/i, with all
+/// receive addresses before the change addresses when both chains are selected
+pub struct AccountAddressIter<'e, C: Verification = All> {
+    chains: Vec<(Chain, AddressIter<'e, C>)>, // Address iterator of every selected chain
+    position: usize,                          // Chain currently being derived
+}
+
+impl<'e, C: Verification> AccountAddressIter<'e, C> {
+    /// Creates an iterator over the range on each selected chain of `account`
+    /// The chain keys are derived up front; an invalid chain key is an error since
+    /// BIP44 has no replacement for it
+    pub fn new(
+        engine: &'e Engine<C>,
+        account: &Xpub,
+        selection: ChainSelection,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Self, Error> {
+        let chains = selection
+            .chains()
+            .iter()
+            .map(|chain| {
+                let chain_key = engine.derive_non_hardened(account, chain.index())?;
+                Ok((
+                    *chain,
+                    AddressIter::new(engine, chain_key, range, script_type)?,
+                ))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self {
+            chains,
+            position: 0,
+        })
+    }
+}
+
+impl<C: Verification> Clone for AccountAddressIter<'_, C> {
+    fn clone(&self) -> Self {
+        Self {
+            chains: self.chains.clone(),
+            position: self.position,
+        }
+    }
+}
+
+impl<C: Verification> Iterator for AccountAddressIter<'_, C> {
+    type Item = (Chain, u32, Xpub, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((chain, addresses)) = self.chains.get_mut(self.position) {
+            if let Some((index, child, address)) = addresses.next() {
+                return Some((*chain, index, child, address));
+            }
+            self.position += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let upper = self.chains[self.position.min(self.chains.len())..]
+            .iter()
+            .try_fold(0usize, |total, (_, addresses)| {
+                total.checked_add(addresses.size_hint().1?)
+            });
+        (0, upper)
+    }
+}
+
+impl<C: Verification> FusedIterator for AccountAddressIter<'_, C> {}
@@ -1,21 +1,67 @@
-use base58::{FromBase58, ToBase58};
-use ripemd::Ripemd160;
-use secp256k1::PublicKey;
-use sha2::{Digest, Sha256};
+use base58::FromBase58;
+use secp256k1::{PublicKey, XOnlyPublicKey};
+
+pub mod address;
+pub mod base58check;
+pub mod bech32;
+pub mod chain;
+pub mod convert;
+pub mod descriptor;
+pub mod engine;
+pub mod error;
+mod hash;
+pub mod inspect;
+pub mod iter;
+pub mod mnemonic;
+pub mod multisig;
+pub mod network;
+pub mod output;
+#[cfg(feature = "parallel")]
+mod parallel;
+pub mod path;
+pub mod purpose;
+pub mod range;
+pub mod script;
+pub mod version;
+mod wordlist;
+pub mod xprv;
+
+pub use address::{Address, AddressMatch, AddressType, ScriptType};
+pub use chain::{Chain, ChainSelection};
+pub use convert::{Conversion, ConversionWarning};
+pub use descriptor::{Descriptor, DescriptorKey, KeyOrigin};
+pub use engine::Engine;
+pub use error::Error;
+pub use inspect::KeyInfo;
+pub use iter::{AccountAddressIter, AddressIter};
+pub use mnemonic::Mnemonic;
+pub use multisig::{Multisig, MultisigType};
+pub use network::Network;
+pub use output::{Format, Record, RecordWriter};
+pub use path::DerivationPath;
+pub use purpose::Purpose;
+pub use range::IndexRange;
+pub use script::Script;
+pub use version::Version;
+pub use xprv::Xprv;
 
 #[derive(Clone)]
 /// Represents an extended public key (xpub) following the BIP32 specification
 /// This structure contains all necessary components to derive child keys and generate Bitcoin addresses
 pub struct Xpub {
-    pub depth: u8,               // Depth in the HD tree
-    pub parent_fingerprint: u32, // Fingerprint of the parent key
-    pub child_number: u32,       // Index of this key
-    pub chain_code: [u8; 32],    // Chain code (32 bytes)
-    pub public_key: PublicKey,   // Compressed public key (33 bytes)
+    pub version: Version,          // SLIP-132 version of the serialized key
+    pub network: Network,          // Network used for address generation
+    pub depth: u8,                 // Depth in the HD tree
+    pub parent_fingerprint: u32,   // Fingerprint of the parent key
+    pub child_number: u32,         // Index of this key
+    pub chain_code: [u8; 32],      // Chain code (32 bytes)
+    pub public_key: PublicKey,     // Compressed public key (33 bytes)
+    pub origin: Option<KeyOrigin>, // Master fingerprint and path from the master key, if known
 }
 
 impl Xpub {
     /// Creates a new extended public key with the provided components
+    /// The version defaults to mainnet xpub on the Bitcoin network
     pub fn new(
         depth: u8,
         parent_fingerprint: u32,
@@ -24,38 +70,101 @@ impl Xpub {
         public_key: PublicKey,
     ) -> Self {
         Self {
+            version: Version::Xpub,
+            network: Network::Bitcoin,
             depth,
             parent_fingerprint,
             child_number,
             chain_code,
             public_key,
+            origin: None,
         }
     }
 
+    /// Returns a copy of this key with a known origin (master fingerprint and path)
+    /// Children derived from the key extend the origin path, so their origin can be
+    /// written as "[fingerprint/path]" in descriptors or PSBT key paths
+    /// The origin is not checked against the key's depth or child number
+    pub fn with_origin(mut self, origin: KeyOrigin) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Origin of the key: the attached origin, or for a master key its own fingerprint
+    /// with an empty path; `None` when the key was parsed without origin information
+    pub fn key_origin(&self) -> Option<KeyOrigin> {
+        match &self.origin {
+            Some(origin) => Some(origin.clone()),
+            None if self.depth == 0 => Some(KeyOrigin::master(self.fingerprint())),
+            None => None,
+        }
+    }
+
+    /// Returns a copy of this key serialized under a different version
+    /// The network falls back to the version's default when the network class changes
+    pub fn with_version(mut self, version: Version) -> Self {
+        if version.is_mainnet() != self.network.is_mainnet() {
+            self.network = version.default_network();
+        }
+        self.version = version;
+        self
+    }
+
+    /// Returns a copy of this key bound to the given network
+    /// Test networks (testnet, testnet4, signet, regtest) share the same version bytes,
+    /// so only networks matching the version's class are accepted
+    pub fn with_network(mut self, network: Network) -> Result<Self, Error> {
+        if network.is_mainnet() != self.version.is_mainnet() {
+            return Err(Error::NetworkMismatch {
+                network,
+                version: self.version,
+            });
+        }
+        self.network = network;
+        Ok(self)
+    }
+
+    /// Re-encodes the same key material under another SLIP-132 version
+    /// The returned conversion reports whether the implied script type or network changed
+    pub fn convert(&self, version: Version) -> Conversion {
+        Conversion::new(self, version)
+    }
+
     /// Converts a Base58 encoded xpub string into an Xpub instance.
-    pub fn from_base58(xpub: &str) -> Result<Self, String> {
+    pub fn from_base58(xpub: &str) -> Result<Self, Error> {
         // Decode the xpub from Base58
-        let decoded = xpub
-            .from_base58()
-            .map_err(|e| format!("Base58 decode error: {:?}", e))?;
+        let decoded = xpub.from_base58()?;
 
         if decoded.len() != 82 {
-            return Err("Invalid xpub length".to_string());
+            return Err(Error::InvalidLength {
+                expected: 82,
+                found: decoded.len(),
+            });
         }
 
+        // bytes [78..82]: checksum over the 78 byte payload
+        let payload = base58check::verify_checksum(&decoded)?;
+
+        Self::from_payload(payload)
+    }
+
+    /// Parses the 78 byte serialized key, without the Base58Check checksum
+    pub(crate) fn from_payload(decoded: &[u8]) -> Result<Self, Error> {
         // Extract components from the decoded xpub
-        // bytes [0..4]: version bytes (not stored)
+        // bytes [0..4]: version bytes
         // bytes [4]: depth
         // bytes [5..9]: parent fingerprint
         // bytes [9..13]: child number
-        // bytes [13..45]: chain cod
+        // bytes [13..45]: chain code
         // bytes [45..78]: public key
+        let version = Version::from_bytes(decoded[0..4].try_into().unwrap())?;
         let depth = decoded[4];
         let parent_fingerprint = u32::from_be_bytes(decoded[5..9].try_into().unwrap());
         let child_number = u32::from_be_bytes(decoded[9..13].try_into().unwrap());
         let chain_code = decoded[13..45].try_into().unwrap();
-        let public_key = PublicKey::from_slice(&decoded[45..78])
-            .map_err(|e| format!("Invalid public key: {}", e))?;
+        let public_key =
+            PublicKey::from_slice(&decoded[45..78]).map_err(Error::InvalidPublicKey)?;
+        check_master_fields(depth, parent_fingerprint, child_number)?;
 
         Ok(Self::new(
             depth,
@@ -63,7 +172,8 @@ impl Xpub {
             child_number,
             chain_code,
             public_key,
-        ))
+        )
+        .with_version(version))
     }
 
     /// Serializes the Xpub into its Base58 string representation
@@ -71,10 +181,7 @@ impl Xpub {
         let mut serialized = [0u8; 78];
 
         // Version bytes (4 bytes)
-        serialized[0] = 0x04;
-        serialized[1] = 0x88;
-        serialized[2] = 0xB2;
-        serialized[3] = 0x1E;
+        serialized[0..4].copy_from_slice(&self.version.to_bytes());
 
         // Depth (1 byte)
         serialized[4] = self.depth;
@@ -90,134 +197,312 @@ impl Xpub {
         // Public key (33 bytes)
         serialized[45..78].copy_from_slice(&self.public_key.serialize());
 
-        // Calculate checksum and create final data
-        let checksum = Sha256::digest(Sha256::digest(serialized));
-        let mut final_data = [0u8; 82];
-        final_data[..78].copy_from_slice(&serialized);
-        final_data[78..82].copy_from_slice(&checksum[..4]);
-
-        final_data.to_base58()
+        // Append checksum and encode
+        base58check::encode_check(&serialized)
     }
 
     /// Generates a legacy P2PKH (Pay to Public Key Hash) Bitcoin address from the public key
     /// 1. Calculates HASH160 (RIPEMD160(SHA256(public_key)))
-    /// 2. Adds version byte (0x00 for mainnet)
+    /// 2. Adds version byte (0x00 for mainnet, 0x6F for test networks)
     /// 3. Adds double SHA256 checksum
     /// 4. Encodes in Base58Check format
     pub fn to_bitcoin_address(&self) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(self.public_key.serialize());
-        let sha256 = hasher.finalize();
+        address::base58_address(self.network.p2pkh_prefix(), &self.pubkey_hash())
+    }
 
-        let pubkey_hash = Ripemd160::digest(sha256);
+    /// Generates a nested SegWit P2SH-P2WPKH address as described in BIP49
+    /// 1. Builds the redeem script OP_0 <HASH160(public_key)>
+    /// 2. Calculates HASH160 of the redeem script, as committed to by its P2SH output
+    /// 3. Adds version byte (0x05 for mainnet, 0xC4 for test networks)
+    /// 4. Encodes in Base58Check format
+    pub fn to_p2sh_p2wpkh_address(&self) -> String {
+        let redeem_script = self
+            .redeem_script(ScriptType::P2shP2wpkh)
+            .expect("P2SH-P2WPKH always has a redeem script");
 
-        let mut address_bytes = [0u8; 25];
-        address_bytes[0] = 0x00;
-        address_bytes[1..21].copy_from_slice(&pubkey_hash);
+        address::base58_address(self.network.p2sh_prefix(), &redeem_script.script_hash())
+    }
 
-        let checksum = &Sha256::digest(Sha256::digest(&address_bytes[..21]))[..4];
-        address_bytes[21..].copy_from_slice(checksum);
+    /// Generates a native SegWit P2WPKH (Pay to Witness Public Key Hash) address (BIP173)
+    /// 1. Calculates HASH160 of the compressed public key (the witness program)
+    /// 2. Encodes witness version 0 and the program with bech32 under the network's HRP
+    pub fn to_p2wpkh_address(&self) -> String {
+        bech32::encode_segwit_address(self.network.bech32_hrp(), 0, &self.pubkey_hash())
+            .expect("20 byte witness v0 program is always valid")
+    }
 
-        address_bytes.to_base58()
+    /// Generates a Taproot P2TR (Pay to Taproot) key path address as described in BIP86
+    /// 1. Takes the x-only internal key from the public key
+    /// 2. Tweaks it with the BIP341 TapTweak hash, committing to an empty script tree
+    /// 3. Encodes witness version 1 and the output key with bech32m (BIP350)
+    pub fn to_p2tr_address(&self) -> String {
+        engine::global().address(self, ScriptType::P2tr)
     }
 
-    /// Derives a non-hardened child Xpub from the current Xpub
-    pub fn derive_non_hardened(&self, index: u32) -> Result<Self, secp256k1::Error> {
-        use hmac::{Hmac, Mac};
-        use sha2::Sha512;
-        type HmacSha512 = Hmac<Sha512>;
-
-        // Verify index is not hardened
-        if index >= 0x8000_0000 {
-            return Err(secp256k1::Error::InvalidTweak); // Hardened keys are not allowed for Xpub
+    /// Computes the BIP86 Taproot output key: internal key tweaked with hash_TapTweak(P)
+    pub fn taproot_output_key(&self) -> XOnlyPublicKey {
+        engine::global().taproot_output_key(self)
+    }
+
+    /// Generates an address of the requested script type from the public key
+    pub fn to_address(&self, script_type: ScriptType) -> String {
+        match script_type {
+            ScriptType::P2pkh => self.to_bitcoin_address(),
+            ScriptType::P2shP2wpkh => self.to_p2sh_p2wpkh_address(),
+            ScriptType::P2wpkh => self.to_p2wpkh_address(),
+            ScriptType::P2tr => self.to_p2tr_address(),
         }
+    }
 
-        // Prepare data for HMAC-SHA512
-        // parent_pubkey (33 bytes) || child_index (4 bytes)
-        let mut data = [0u8; 37];
-        data[..33].copy_from_slice(&self.public_key.serialize());
-        data[33..].copy_from_slice(&index.to_be_bytes());
-
-        // Generate child key material using HMAC-SHA512
-        let mut mac =
-            HmacSha512::new_from_slice(&self.chain_code).expect("HMAC can take a key of any size");
-        mac.update(&data);
-        let result = mac.finalize().into_bytes();
-
-        // Split the result into two 32-byte halves
-        let (i_l, i_r) = result.split_at(32);
-
-        // Compute the child public key
-        let secp = secp256k1::Secp256k1::new();
-        let tweak = secp256k1::SecretKey::from_slice(i_l)?;
-        let child_pubkey = self
-            .public_key
-            .add_exp_tweak(&secp, &tweak.into())
-            .map_err(|_| secp256k1::Error::InvalidTweak)?;
-
-        let mut chain_code = [0u8; 32];
-        chain_code.copy_from_slice(i_r);
-
-        // Create the child Xpub
-        Ok(Self {
-            depth: self.depth + 1,
-            parent_fingerprint: self.fingerprint(),
-            child_number: index,
-            chain_code,
-            public_key: child_pubkey,
-        })
+    /// Builds the output script (scriptPubKey) paying to the public key
+    /// P2PKH: OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+    /// P2SH-P2WPKH: OP_HASH160 <HASH160(redeem_script)> OP_EQUAL
+    /// P2WPKH: OP_0 <20 bytes>, P2TR: OP_1 <32 byte output key>
+    pub fn script_pubkey(&self, script_type: ScriptType) -> Script {
+        match script_type {
+            ScriptType::P2pkh => Script::p2pkh(&self.pubkey_hash()),
+            ScriptType::P2shP2wpkh => self.script_pubkey(ScriptType::P2wpkh).to_p2sh(),
+            ScriptType::P2wpkh => Script::p2wpkh(&self.pubkey_hash()),
+            ScriptType::P2tr => Script::p2tr(&self.taproot_output_key().serialize()),
+        }
     }
 
-    /// Generates multiple Bitcoin addresses using BIP32 derivation path
-    pub fn derive_bip32_addresses(&self, count: u32) -> Result<Vec<String>, String> {
-        let mut addresses = Vec::with_capacity(count as usize);
-        let current = self.clone();
-
-        // Generate sequential addresses
-        for i in 0..count {
-            match current.derive_non_hardened(i) {
-                Ok(child) => {
-                    addresses.push(child.to_bitcoin_address());
-                }
-                Err(e) => {
-                    return Err(format!("Error deriving child {}: {}", i, e));
-                }
-            }
+    /// Returns the redeem script revealed when spending a P2SH output, which for
+    /// P2SH-P2WPKH is the P2WPKH script OP_0 <20 bytes>
+    /// Other single key script types have no redeem script, and none has a witness script
+    pub fn redeem_script(&self, script_type: ScriptType) -> Option<Script> {
+        match script_type {
+            ScriptType::P2shP2wpkh => Some(self.script_pubkey(ScriptType::P2wpkh)),
+            _ => None,
         }
+    }
+
+    /// Calculates HASH160 (RIPEMD160(SHA256(public_key))) of the compressed public key
+    pub fn pubkey_hash(&self) -> [u8; 20] {
+        hash::hash160(&self.public_key.serialize())
+    }
 
-        Ok(addresses)
+    /// Formats the compressed public key as lowercase hex
+    pub fn public_key_hex(&self) -> String {
+        hash::hex(&self.public_key.serialize())
+    }
+
+    /// Derives a non-hardened child Xpub from the current Xpub
+    /// Returns `Error::InvalidTweak` for the rare indices BIP32 says to skip
+    pub fn derive_non_hardened(&self, index: u32) -> Result<Self, Error> {
+        engine::global().derive_non_hardened(self, index)
+    }
+
+    /// Derives the key at the given path, relative to this Xpub
+    /// Only non-hardened steps can be derived from a public key
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Error> {
+        engine::global().derive_path(self, path)
+    }
+
+    /// Generates multiple Bitcoin addresses below a derivation path relative to this Xpub
+    /// The trailing wildcard of the path is replaced by 0..count; a path without a
+    /// wildcard is treated as the parent of the generated addresses
+    pub fn derive_path_addresses(
+        &self,
+        path: &DerivationPath,
+        count: u32,
+        script_type: ScriptType,
+    ) -> Result<Vec<String>, Error> {
+        let range = IndexRange::from_count(0, count)?;
+        Ok(strip_indices(self.derive_path_address_range(
+            path,
+            range,
+            script_type,
+        )?))
+    }
+
+    /// Generates Bitcoin addresses for a range of wildcard indices below a derivation path
+    /// Each address is returned with its index
+    pub fn derive_path_address_range(
+        &self,
+        path: &DerivationPath,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Vec<(u32, String)>, Error> {
+        Ok(self
+            .path_address_iter(path, range, script_type)?
+            .map(|(i, _, address)| (i, address))
+            .collect())
+    }
+
+    /// Lazily derives the wildcard indices in the range below a derivation path, yielding
+    /// (index, child Xpub, address); the parent of the wildcard is derived up front
+    pub fn path_address_iter(
+        &self,
+        path: &DerivationPath,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<AddressIter<'static>, Error> {
+        let parent = self.derive_path(&DerivationPath::new(path.steps().to_vec()))?;
+        engine::global().address_iter(&parent, range, script_type)
+    }
+
+    /// Generates multiple Bitcoin addresses using BIP32 derivation path
+    /// The script type is implied by the version (xpub: P2PKH, ypub: P2SH-P2WPKH, zpub: P2WPKH);
+    /// multisig versions (Ypub, Zpub, ...) return `Error::MultisigVersion`
+    pub fn derive_bip32_addresses(&self, count: u32) -> Result<Vec<String>, Error> {
+        self.derive_bip32_addresses_with(count, self.version.single_key_script_type()?)
+    }
+
+    /// Generates multiple Bitcoin addresses of the given script type using BIP32 derivation path
+    pub fn derive_bip32_addresses_with(
+        &self,
+        count: u32,
+        script_type: ScriptType,
+    ) -> Result<Vec<String>, Error> {
+        let range = IndexRange::from_count(0, count)?;
+        Ok(strip_indices(
+            self.derive_bip32_address_range(range, script_type)?,
+        ))
+    }
+
+    /// Generates Bitcoin addresses for a range of child indices using BIP32 derivation path
+    /// Each address is returned with its index, so pools can be topped up from any start;
+    /// indices BIP32 says to skip are left out
+    pub fn derive_bip32_address_range(
+        &self,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Vec<(u32, String)>, Error> {
+        Ok(self
+            .address_iter(range, script_type)?
+            .map(|(i, _, address)| (i, address))
+            .collect())
+    }
+
+    /// Lazily derives the children in the range, yielding (index, child Xpub, address)
+    /// Nothing is derived until the iterator is advanced; indices that produce an
+    /// invalid key are skipped as BIP32 requires
+    pub fn address_iter(
+        &self,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<AddressIter<'static>, Error> {
+        engine::global().address_iter(self, range, script_type)
     }
 
     /// Generates multiple Bitcoin addresses using BIP44 derivation path
     /// Follows m/44'/0'/0'/0/i path structure
-    pub fn derive_bip44_addresses(&self, count: u32) -> Result<Vec<String>, String> {
-        let mut addresses = Vec::with_capacity(count as usize);
-
-        //BIP44 path: m/44'/0'/0'/0/i
-        let account = self
-            .derive_non_hardened(0)
-            .map_err(|e| format!("Error deriving account: {}", e))?;
-
-        // Generate addresses at m/44'/0'/0'/0/i
-        for i in 0..count {
-            match account.derive_non_hardened(i) {
-                Ok(child) => {
-                    addresses.push(child.to_bitcoin_address());
-                }
-                Err(e) => {
-                    return Err(format!("Error deriving child {}: {}", i, e));
-                }
-            }
-        }
-        Ok(addresses)
+    /// The script type is implied by the version (xpub: P2PKH, ypub: P2SH-P2WPKH, zpub: P2WPKH);
+    /// multisig versions (Ypub, Zpub, ...) return `Error::MultisigVersion`
+    pub fn derive_bip44_addresses(&self, count: u32) -> Result<Vec<String>, Error> {
+        self.derive_bip44_addresses_with(count, self.version.single_key_script_type()?)
+    }
+
+    /// Generates multiple Bitcoin addresses of the given script type using BIP44 derivation path
+    /// The same account/0/i layout is used by BIP49, BIP84 and BIP86 accounts
+    pub fn derive_bip44_addresses_with(
+        &self,
+        count: u32,
+        script_type: ScriptType,
+    ) -> Result<Vec<String>, Error> {
+        self.derive_bip44_chain_addresses(Chain::External, count, script_type)
+    }
+
+    /// Generates multiple Bitcoin addresses on one chain of a BIP44 account
+    /// Follows account/chain/i, with chain 0 for receive and 1 for change addresses
+    pub fn derive_bip44_chain_addresses(
+        &self,
+        chain: Chain,
+        count: u32,
+        script_type: ScriptType,
+    ) -> Result<Vec<String>, Error> {
+        let range = IndexRange::from_count(0, count)?;
+        let addresses = self.derive_bip44_address_range(chain.into(), range, script_type)?;
+        Ok(addresses
+            .into_iter()
+            .map(|(_, _, address)| address)
+            .collect())
+    }
+
+    /// Generates multiple Bitcoin addresses on the selected chains of a BIP44 account
+    /// Each address is labeled with its chain and index; with both chains selected, all
+    /// receive addresses are returned before the change addresses
+    pub fn derive_bip44_selected_addresses(
+        &self,
+        selection: ChainSelection,
+        count: u32,
+        script_type: ScriptType,
+    ) -> Result<Vec<(Chain, u32, String)>, Error> {
+        let range = IndexRange::from_count(0, count)?;
+        self.derive_bip44_address_range(selection, range, script_type)
+    }
+
+    /// Generates Bitcoin addresses for a range of indices on the selected chains of a
+    /// BIP44 account, following account/chain/i
+    pub fn derive_bip44_address_range(
+        &self,
+        selection: ChainSelection,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Vec<(Chain, u32, String)>, Error> {
+        Ok(self
+            .bip44_address_iter(selection, range, script_type)?
+            .map(|(chain, i, _, address)| (chain, i, address))
+            .collect())
+    }
+
+    /// Lazily derives addresses on the selected chains of a BIP44 account, yielding
+    /// (chain, index, child Xpub, address); receive addresses come before change addresses
+    pub fn bip44_address_iter(
+        &self,
+        selection: ChainSelection,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<AccountAddressIter<'static>, Error> {
+        engine::global().bip44_address_iter(self, selection, range, script_type)
+    }
+
+    /// Searches the selected chains of a BIP44 style account for an address
+    /// Indices in the range are scanned on each chain in turn (receive before change);
+    /// the script type is detected from the address. Returns None when nothing matches
+    pub fn find_address(
+        &self,
+        address: &str,
+        selection: ChainSelection,
+        range: IndexRange,
+    ) -> Result<Option<AddressMatch>, Error> {
+        let script_type = ScriptType::from_address(address, self.network)?;
+        // Bech32 addresses may be written in upper case
+        let address = match script_type {
+            ScriptType::P2wpkh | ScriptType::P2tr => address.to_ascii_lowercase(),
+            ScriptType::P2pkh | ScriptType::P2shP2wpkh => address.to_string(),
+        };
+
+        let found = self
+            .bip44_address_iter(selection, range, script_type)?
+            .find(|(_, _, _, derived)| *derived == address)
+            .map(|(chain, index, _, _)| AddressMatch {
+                chain,
+                index,
+                script_type,
+            });
+        Ok(found)
     }
 
     /// Calculates the fingerprint (first 4 bytes of HASH160) of the current public key.
     /// Used for child key derivation and parent identification.
     pub fn fingerprint(&self) -> u32 {
-        let hash = Sha256::digest(self.public_key.serialize());
-        let hash160 = Ripemd160::digest(hash);
+        let hash160 = self.pubkey_hash();
 
         u32::from_be_bytes(hash160[0..4].try_into().unwrap())
     }
 }
+
+/// Checks that a master key (depth 0) has no parent fingerprint or child number
+fn check_master_fields(depth: u8, parent_fingerprint: u32, child_number: u32) -> Result<(), Error> {
+    if depth == 0 && (parent_fingerprint != 0 || child_number != 0) {
+        return Err(Error::InvalidMasterKey);
+    }
+    Ok(())
+}
+
+/// Drops the indices from indexed address lists returned by the range APIs
+fn strip_indices(addresses: Vec<(u32, String)>) -> Vec<String> {
+    addresses.into_iter().map(|(_, address)| address).collect()
+}
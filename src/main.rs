@@ -1,5 +1,13 @@
-use bip_tools::Xpub;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use bip_tools::{
+    Address, Chain, ChainSelection, DerivationPath, Descriptor, Error, Format, IndexRange, KeyInfo,
+    KeyOrigin, Mnemonic, Multisig, MultisigType, Network, Purpose, Record, RecordWriter, Script,
+    ScriptType, Version, Xpub,
+};
 use clap::{Args, Parser, Subcommand};
+use zeroize::Zeroize;
 
 #[derive(Debug, Parser)]
 #[command(
@@ -13,6 +21,12 @@ struct Cli {
     /// Subcommands for different address generation methods
     #[command(subcommand)]
     commands: Commands,
+    /// Output format (text, json, csv, ndjson)
+    ///
+    /// Machine readable formats print one record per item and send header lines to
+    /// standard error
+    #[arg(long, global = true, default_value = "text")]
+    format: Format,
 }
 
 #[derive(Debug, Subcommand)]
@@ -20,7 +34,21 @@ enum Commands {
     /// Generate public addresses from a BIP32 extended public key
     Bip32(AddressGeneratorArgs),
     /// Generate public addresses from a BIP44 extended public key
-    Bip44(AddressGeneratorArgs),
+    Bip44(Bip44Args),
+    /// Print the account extended public key of a BIP39 mnemonic
+    Mnemonic(MnemonicArgs),
+    /// Generate addresses from an output descriptor (pkh, sh(wpkh), wpkh, tr)
+    Descriptor(DescriptorArgs),
+    /// Generate k-of-n multisig scripts and addresses from several extended public keys
+    Multisig(MultisigArgs),
+    /// Find the chain and index of an address below an account extended public key
+    Find(FindArgs),
+    /// Decode an extended public key and print its version, depth, fingerprints and keys
+    Inspect(InspectArgs),
+    /// Re-encode an extended public key under another SLIP-132 version (e.g. zpub to xpub)
+    Convert(ConvertArgs),
+    /// Check an address and print its network, type and scriptPubKey
+    Validate(ValidateArgs),
 }
 
 /// Common arguments for both BIP32 adn BIP44 address generation
@@ -28,58 +56,896 @@ enum Commands {
 #[command(flatten_help = true)]
 struct AddressGeneratorArgs {
     extended_public_key: String,
+    /// Number of addresses to generate (may be omitted when --end is given)
+    #[arg(required_unless_present = "end")]
+    count: Option<u32>,
+    /// First child index to derive
+    #[arg(long, default_value_t = 0)]
+    start: u32,
+    /// Exclusive end of the child index range, used instead of COUNT
+    #[arg(long, conflicts_with = "count")]
+    end: Option<u32>,
+    /// Network for generated addresses (bitcoin, testnet, testnet4, signet, regtest)
+    ///
+    /// Defaults to the network implied by the key's version bytes
+    #[arg(long)]
+    network: Option<Network>,
+    /// Address script type (p2pkh, p2sh-p2wpkh, p2wpkh, p2tr)
+    ///
+    /// Defaults to the script type implied by the key's version bytes
+    /// (xpub/tpub: p2pkh, ypub/upub: p2sh-p2wpkh, zpub/vpub: p2wpkh); required for
+    /// the multisig versions (Ypub/Zpub/Upub/Vpub)
+    #[arg(long)]
+    script_type: Option<ScriptType>,
+    /// Derivation path relative to the key (e.g. "m/0/5/12" or "1/*")
+    ///
+    /// Replaces the default BIP32/BIP44 layout; addresses are generated for the
+    /// trailing wildcard, which is implied when omitted
+    #[arg(long)]
+    path: Option<DerivationPath>,
+    /// Origin of the key: master fingerprint and path, e.g. "[73c5da0a/84'/0'/0']"
+    ///
+    /// Shown as the full origin of each derived key; master keys (depth 0) are
+    /// their own origin
+    #[arg(long)]
+    origin: Option<KeyOrigin>,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Output options of the commands generating single key addresses
+#[derive(Debug, Args)]
+struct OutputArgs {
+    /// Include the public key (hex) of each address
+    #[arg(long)]
+    pubkey: bool,
+    /// Include the scriptPubKey (hex) of each address, and its redeem script for P2SH-P2WPKH
+    #[arg(long)]
+    script_pubkey: bool,
+    /// Also write the scripts selected with --script-pubkey as assembly
+    #[arg(long, requires = "script_pubkey")]
+    asm: bool,
+    /// Derive addresses in parallel on this many threads (0 uses every core)
+    ///
+    /// The output is identical to sequential derivation; requires the "parallel" feature
+    #[arg(long)]
+    threads: Option<usize>,
+}
+
+/// Arguments for BIP44 address generation
+#[derive(Debug, Args)]
+#[command(flatten_help = true)]
+struct Bip44Args {
+    #[command(flatten)]
+    common: AddressGeneratorArgs,
+    /// Account chain to derive (external, internal, both)
+    ///
+    /// "both" prints receive and change addresses, each labeled with its chain
+    #[arg(long, default_value = "external", conflicts_with = "path")]
+    change: ChainSelection,
+}
+
+/// Arguments for account key derivation from a BIP39 mnemonic
+#[derive(Debug, Args)]
+#[command(flatten_help = true)]
+struct MnemonicArgs {
+    /// BIP39 mnemonic sentence (English wordlist)
+    ///
+    /// Read from the first line of standard input when omitted, which keeps it
+    /// out of the shell history
+    mnemonic: Option<String>,
+    /// Optional BIP39 passphrase
+    ///
+    /// Visible in the shell history and process list; prefer --passphrase-stdin
+    #[arg(long, default_value = "")]
+    passphrase: String,
+    /// Read the BIP39 passphrase from the next line of standard input
+    ///
+    /// The passphrase is the second line when the mnemonic is read from standard
+    /// input as well, otherwise the first
+    #[arg(long, conflicts_with = "passphrase")]
+    passphrase_stdin: bool,
+    /// Account purpose (44, 49, 84, 86)
+    #[arg(long, default_value = "44")]
+    purpose: Purpose,
+    /// Account number
+    #[arg(long, default_value_t = 0)]
+    account: u32,
+    /// Network of the account (bitcoin, testnet, testnet4, signet, regtest)
+    #[arg(long, default_value = "bitcoin")]
+    network: Network,
+}
+
+/// Arguments for address generation from an output descriptor
+#[derive(Debug, Args)]
+#[command(flatten_help = true)]
+struct DescriptorArgs {
+    /// Output descriptor, e.g. "wpkh([d34db33f/84'/0'/0']xpub.../0/*)#checksum"
+    ///
+    /// The checksum is verified when present
+    descriptor: String,
+    /// Number of addresses to generate for a descriptor ending with a wildcard
+    count: Option<u32>,
+    /// First wildcard index to derive
+    #[arg(long, default_value_t = 0)]
+    start: u32,
+    /// Exclusive end of the wildcard index range, used instead of COUNT
+    #[arg(long, conflicts_with = "count")]
+    end: Option<u32>,
+    /// Network for generated addresses (bitcoin, testnet, testnet4, signet, regtest)
+    ///
+    /// Defaults to the network implied by the key's version bytes
+    #[arg(long)]
+    network: Option<Network>,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Arguments for multisig address generation
+#[derive(Debug, Args)]
+#[command(flatten_help = true)]
+struct MultisigArgs {
+    /// Number of signatures required
+    threshold: usize,
+    /// Extended public keys of the cosigners
+    #[arg(required = true)]
+    extended_public_keys: Vec<String>,
+    /// Number of addresses to generate (may be omitted when --end is given)
+    #[arg(long, required_unless_present = "end")]
+    count: Option<u32>,
+    /// First child index to derive
+    #[arg(long, default_value_t = 0)]
+    start: u32,
+    /// Exclusive end of the child index range, used instead of --count
+    #[arg(long, conflicts_with = "count")]
+    end: Option<u32>,
+    /// Multisig address type (p2sh, p2sh-p2wsh, p2wsh)
+    #[arg(long = "type", default_value = "p2wsh")]
+    multisig_type: MultisigType,
+    /// Derivation path relative to each cosigner key, ending with the wildcard
+    #[arg(long, default_value = "0/*")]
+    path: DerivationPath,
+    /// Keep the keys in the given order (multi) instead of sorting them (BIP67 sortedmulti)
+    #[arg(long)]
+    unsorted: bool,
+    /// Include the scriptPubKey, redeem script and witness script (hex) of each address,
+    /// where they apply to the address type
+    #[arg(long)]
+    script_pubkey: bool,
+    /// Also write the scripts selected with --script-pubkey as assembly
+    #[arg(long, requires = "script_pubkey")]
+    asm: bool,
+    /// Network for generated addresses (bitcoin, testnet, testnet4, signet, regtest)
+    ///
+    /// Defaults to the network implied by the keys' version bytes
+    #[arg(long)]
+    network: Option<Network>,
+}
+
+/// Arguments for address lookup
+#[derive(Debug, Args)]
+#[command(flatten_help = true)]
+struct FindArgs {
+    /// Account extended public key (e.g. m/84'/0'/0')
+    extended_public_key: String,
+    /// Address to look up (p2pkh, p2sh-p2wpkh, p2wpkh or p2tr)
+    address: String,
+    /// Number of indices to scan on each chain, starting at index 0
+    ///
+    /// Every index below the count is checked, whether or not the addresses before
+    /// it were used; this is not a wallet gap limit
+    #[arg(long, default_value_t = 20)]
     count: u32,
+    /// Highest index to scan on each chain, used instead of --count
+    #[arg(long, conflicts_with = "count")]
+    max_index: Option<u32>,
+    /// Account chains to scan (external, internal, both)
+    #[arg(long, default_value = "both")]
+    change: ChainSelection,
+    /// Network of the address (bitcoin, testnet, testnet4, signet, regtest)
+    ///
+    /// Defaults to the network implied by the key's version bytes
+    #[arg(long)]
+    network: Option<Network>,
+}
+
+/// Arguments for extended key inspection
+#[derive(Debug, Args)]
+#[command(flatten_help = true)]
+struct InspectArgs {
+    /// Extended public key to decode (xpub, ypub, zpub, tpub, ...)
+    extended_public_key: String,
+}
+
+/// Arguments for SLIP-132 version conversion
+#[derive(Debug, Args)]
+#[command(flatten_help = true)]
+struct ConvertArgs {
+    /// Extended public key to convert
+    extended_public_key: String,
+    /// Target version prefix (xpub, ypub, Ypub, zpub, Zpub, tpub, upub, Upub, vpub, Vpub)
+    version: Version,
+}
+
+/// Arguments for address validation
+#[derive(Debug, Args)]
+#[command(flatten_help = true)]
+struct ValidateArgs {
+    /// Address to validate (Base58 P2PKH/P2SH or bech32/bech32m segwit)
+    address: String,
+    /// Network the address must belong to (bitcoin, testnet, testnet4, signet, regtest)
+    ///
+    /// Defaults to the network detected from the address
+    #[arg(long)]
+    network: Option<Network>,
+}
+
+impl FindArgs {
+    /// Returns the scanned index range, from --count or --max-index
+    fn range(&self) -> Result<IndexRange, Error> {
+        match self.max_index {
+            Some(max_index) => IndexRange::new(0, max_index.saturating_add(1)),
+            None => IndexRange::from_count(0, self.count),
+        }
+    }
+}
+
+impl MultisigArgs {
+    /// Parses the cosigner keys and builds the multisig policy
+    fn multisig(&self) -> Result<Multisig, Error> {
+        let xpubs = self
+            .extended_public_keys
+            .iter()
+            .map(|xpub| Xpub::from_base58(xpub))
+            .collect::<Result<Vec<_>, _>>()?;
+        let xpubs = match self.network {
+            Some(network) => xpubs
+                .into_iter()
+                .map(|xpub| xpub.with_network(network))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => xpubs,
+        };
+        Ok(Multisig::new(self.threshold, xpubs)?.with_sorted(!self.unsorted))
+    }
+}
+
+impl DescriptorArgs {
+    /// Parses the descriptor and applies the requested network
+    fn descriptor(&self) -> Result<Descriptor, Error> {
+        let descriptor: Descriptor = self.descriptor.parse()?;
+        match self.network {
+            Some(network) => descriptor.with_network(network),
+            None => Ok(descriptor),
+        }
+    }
+}
+
+impl MnemonicArgs {
+    /// Parses the mnemonic from the argument or standard input
+    fn mnemonic(&self) -> Result<Mnemonic, Box<dyn std::error::Error>> {
+        match &self.mnemonic {
+            Some(phrase) => Ok(phrase.parse()?),
+            None => {
+                let mut phrase = String::new();
+                io::stdin().lock().read_line(&mut phrase)?;
+                let mnemonic = phrase.parse();
+                phrase.zeroize();
+                Ok(mnemonic?)
+            }
+        }
+    }
+
+    /// Returns the passphrase from --passphrase, or the next line of standard input
+    /// with --passphrase-stdin; leading and trailing spaces are part of the passphrase
+    fn passphrase(&self) -> io::Result<String> {
+        if !self.passphrase_stdin {
+            return Ok(self.passphrase.clone());
+        }
+        let mut passphrase = String::new();
+        io::stdin().lock().read_line(&mut passphrase)?;
+        let len = passphrase.trim_end_matches(['\r', '\n']).len();
+        passphrase.truncate(len);
+        Ok(passphrase)
+    }
+}
+
+impl AddressGeneratorArgs {
+    /// Parses the extended public key and applies the requested network and origin
+    fn xpub(&self) -> Result<Xpub, Error> {
+        let mut xpub = Xpub::from_base58(&self.extended_public_key)?;
+        if let Some(origin) = &self.origin {
+            xpub = xpub.with_origin(origin.clone());
+        }
+        match self.network {
+            Some(network) => xpub.with_network(network),
+            None => Ok(xpub),
+        }
+    }
+
+    /// Returns the requested index range, from --start and either COUNT or --end
+    fn range(&self) -> Result<IndexRange, Box<dyn std::error::Error>> {
+        index_range(self.start, self.count, self.end)
+    }
+
+    /// Returns the requested script type, falling back to the one implied by the key
+    /// Multisig key versions have no implied single key type and need --script-type
+    fn script_type(&self, xpub: &Xpub) -> Result<ScriptType, Error> {
+        match self.script_type {
+            Some(script_type) => Ok(script_type),
+            None => xpub.version.single_key_script_type(),
+        }
+    }
+}
+
+/// Builds the index range from --start and either COUNT or --end
+fn index_range(
+    start: u32,
+    count: Option<u32>,
+    end: Option<u32>,
+) -> Result<IndexRange, Box<dyn std::error::Error>> {
+    match (count, end) {
+        (_, Some(end)) => Ok(IndexRange::new(start, end)?),
+        (Some(count), None) => Ok(IndexRange::from_count(start, count)?),
+        (None, None) => Err("either COUNT or --end is required".into()),
+    }
+}
+
+/// Records written to standard output in the requested format
+type Output = RecordWriter<io::StdoutLock<'static>, io::Stderr>;
+
+/// Label shown with an address: its account chain or descriptor branch
+#[derive(Debug, Clone, Copy)]
+enum Label {
+    Chain(Chain),
+    Branch(usize),
+}
+
+impl Label {
+    /// Adds the label as a "chain" or "branch" field
+    fn add_to(self, record: Record) -> Record {
+        match self {
+            Label::Chain(chain) => record.with("chain", chain.name()),
+            Label::Branch(branch) => record.with("branch", branch),
+        }
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Label::Chain(chain) => write!(f, "{}", chain),
+            Label::Branch(branch) => write!(f, "branch {}", branch),
+        }
+    }
+}
+
+/// A derived single key address, written as one record
+struct KeyAddress<'a> {
+    index: Option<u32>,      // Wildcard index, absent for descriptors without one
+    label: Option<Label>,    // Chain or branch shown with the address
+    path: DerivationPath,    // Derivation path of the key, relative to the given key
+    key: &'a Xpub,           // Key paying to the address
+    script_type: ScriptType, // Script type of the address
+    address: &'a str,        // Encoded address
+}
+
+impl KeyAddress<'_> {
+    /// Writes the address record, with the optional columns requested in `options`
+    fn write(&self, output: &mut Output, options: &OutputArgs) -> io::Result<()> {
+        let mut record = Record::new().with("index", self.index);
+        if let Some(label) = self.label {
+            record = label.add_to(record);
+        }
+        // The full path from the master key when the origin is known
+        let key_origin = self.key.key_origin();
+        let path = match &key_origin {
+            Some(origin) => origin.path.to_string(),
+            None => self.path.to_relative_string(),
+        };
+        let mut record = record
+            .with("path", path)
+            .with("script_type", self.script_type.name())
+            .with("address", self.address);
+        let public_key = options.pubkey.then(|| self.key.public_key_hex());
+        let origin = key_origin.map(|origin| format!("[{}]", origin));
+        if let Some(public_key) = &public_key {
+            record = record.with("public_key", public_key.as_str());
+        }
+        if let Some(origin) = &origin {
+            record = record.with("origin", origin.as_str());
+        }
+        let scripts = if options.script_pubkey {
+            let mut scripts = vec![(
+                ScriptColumn::ScriptPubkey,
+                self.key.script_pubkey(self.script_type),
+            )];
+            if let Some(redeem_script) = self.key.redeem_script(self.script_type) {
+                scripts.push((ScriptColumn::RedeemScript, redeem_script));
+            }
+            script_fields(scripts, options.asm)
+        } else {
+            Vec::new()
+        };
+        for (field, _, value) in &scripts {
+            record = record.with(field, value.as_str());
+        }
+
+        output.write(&record, || {
+            let mut text = match (self.index, self.label) {
+                (Some(i), Some(label)) => format!("Child {} ({}): {}", i, label, self.address),
+                (Some(i), None) => format!("Child {}: {}", i, self.address),
+                (None, Some(Label::Branch(branch))) => {
+                    format!("Branch {}: {}", branch, self.address)
+                }
+                (None, _) => self.address.to_string(),
+            };
+            if let Some(public_key) = &public_key {
+                text.push_str(&format!("\n  Public key: {}", public_key));
+            }
+            if let Some(origin) = &origin {
+                text.push_str(&format!("\n  Key origin: {}", origin));
+            }
+            for (_, label, value) in &scripts {
+                text.push_str(&format!("\n  {}: {}", label, value));
+            }
+            text
+        })
+    }
+}
+
+/// Script written as an optional column of an address record
+#[derive(Debug, Clone, Copy)]
+enum ScriptColumn {
+    ScriptPubkey,
+    RedeemScript,
+    WitnessScript,
+}
+
+impl ScriptColumn {
+    /// Record field and text label of the hex column
+    fn hex_field(self) -> (&'static str, &'static str) {
+        match self {
+            ScriptColumn::ScriptPubkey => ("script_pubkey", "scriptPubKey"),
+            ScriptColumn::RedeemScript => ("redeem_script", "Redeem script"),
+            ScriptColumn::WitnessScript => ("witness_script", "Witness script"),
+        }
+    }
+
+    /// Record field and text label of the assembly column
+    fn asm_field(self) -> (&'static str, &'static str) {
+        match self {
+            ScriptColumn::ScriptPubkey => ("script_pubkey_asm", "scriptPubKey asm"),
+            ScriptColumn::RedeemScript => ("redeem_script_asm", "Redeem script asm"),
+            ScriptColumn::WitnessScript => ("witness_script_asm", "Witness script asm"),
+        }
+    }
+}
+
+/// Lists the record field, text label and value of each script column, as hex
+/// followed by assembly when `asm` is set
+fn script_fields(
+    scripts: Vec<(ScriptColumn, Script)>,
+    asm: bool,
+) -> Vec<(&'static str, &'static str, String)> {
+    let mut fields = Vec::new();
+    for (column, script) in scripts {
+        let (field, label) = column.hex_field();
+        fields.push((field, label, script.to_hex()));
+        if asm {
+            let (field, label) = column.asm_field();
+            fields.push((field, label, script.to_asm()));
+        }
+    }
+    fields
+}
+
+/// Number of indices derived in parallel before they are written
+#[cfg(feature = "parallel")]
+const PARALLEL_CHUNK: u32 = 1 << 16;
+
+/// Writes the addresses of the children of `parent` in the range as soon as they are
+/// derived; `path` is the path of `parent`, ending with the wildcard
+/// With --threads, chunks of the range are derived in parallel and written in index order
+fn write_addresses(
+    output: &mut Output,
+    parent: &Xpub,
+    path: &DerivationPath,
+    label: Option<Label>,
+    range: IndexRange,
+    script_type: ScriptType,
+    options: &OutputArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut write = |index: u32, key: &Xpub, address: &str| {
+        KeyAddress {
+            index: Some(index),
+            label,
+            path: path.child(index),
+            key,
+            script_type,
+            address,
+        }
+        .write(output, options)
+    };
+
+    #[cfg(feature = "parallel")]
+    if let Some(threads) = options.threads {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()?;
+        let engine = bip_tools::engine::global();
+        for chunk in range.chunks(PARALLEL_CHUNK) {
+            let children = pool.install(|| engine.par_children(parent, chunk, script_type))?;
+            for (i, child, address) in children {
+                write(i, &child, &address)?;
+            }
+        }
+        return Ok(());
+    }
+    #[cfg(not(feature = "parallel"))]
+    if options.threads.is_some() {
+        return Err("--threads requires bip-tools to be built with the parallel feature".into());
+    }
+
+    for (i, child, address) in parent.address_iter(range, script_type)? {
+        write(i, &child, &address)?;
+    }
+    Ok(())
 }
 
 /// Main entry point of the application
 /// Parses command line arguments and executes the requested operation
 ///
-/// # Returns
-/// * `Result<(), Box<dyn std::error::Error>>` - Success or error message
-///
 /// # Error
-/// Returns error if:
+/// Prints the error message to stderr and exits with code 1 if:
 /// - Invalid xpub format
 /// - Address derivation fails
 /// - Other unexpected errors occur
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+fn main() {
+    if let Err(e) = run(Cli::parse()) {
+        // Streamed output stops quietly when the reader goes away (e.g. piped into head)
+        if e.downcast_ref::<io::Error>()
+            .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+        {
+            return;
+        }
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Executes the parsed subcommand
+fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let mut output = RecordWriter::new(io::stdout().lock(), cli.format).with_notes(io::stderr());
 
     // Match on the subcommand and execute corresponding functionality
     match cli.commands {
         Commands::Bip32(args) => {
-            let xpub = Xpub::from_base58(&args.extended_public_key)?;
-            println!("Generating {} BIP-32 addresses: ", args.count);
+            let xpub = args.xpub()?;
+            let script_type = args.script_type(&xpub)?;
+            let range = args.range()?;
 
-            match xpub.derive_bip32_addresses(args.count) {
-                Ok(addresses) => {
-                    // Print each derived address with its index
-                    for (i, address) in addresses.iter().enumerate() {
-                        println!("Child {}: {}", i, address);
+            let (parent, path) = match &args.path {
+                Some(path) => {
+                    output.note(&format!(
+                        "Generating {} addresses for {}:",
+                        range.len(),
+                        path.to_relative_string()
+                    ))?;
+                    let parent = xpub.derive_path(&DerivationPath::new(path.steps().to_vec()))?;
+                    (parent, path.clone().with_wildcard())
+                }
+                None => {
+                    output.note(&format!("Generating {} BIP-32 addresses: ", range.len()))?;
+                    (xpub, DerivationPath::default().with_wildcard())
+                }
+            };
+
+            write_addresses(
+                &mut output,
+                &parent,
+                &path,
+                None,
+                range,
+                script_type,
+                &args.output,
+            )?;
+        }
+        Commands::Bip44(Bip44Args {
+            common: args,
+            change,
+        }) => {
+            let xpub = args.xpub()?;
+            let script_type = args.script_type(&xpub)?;
+            let range = args.range()?;
+
+            // Parent key and path of each chain, labeled by chain when both are shown
+            let parents: Result<Vec<(Option<Label>, Xpub, DerivationPath)>, Error> =
+                match &args.path {
+                    Some(path) => {
+                        output.note(&format!(
+                            "Generating {} addresses for {}:",
+                            range.len(),
+                            path.to_relative_string()
+                        ))?;
+                        xpub.derive_path(&DerivationPath::new(path.steps().to_vec()))
+                            .map(|parent| vec![(None, parent, path.clone().with_wildcard())])
+                    }
+                    None => {
+                        output.note(&format!("Generating {} BIP44 addresses:", range.len()))?;
+                        let label_chains = change == ChainSelection::Both;
+                        change
+                            .chains()
+                            .iter()
+                            .map(|chain| {
+                                let parent = xpub.derive_non_hardened(chain.index())?;
+                                let path = DerivationPath::new(vec![chain.index()]).with_wildcard();
+                                Ok((label_chains.then_some(Label::Chain(*chain)), parent, path))
+                            })
+                            .collect()
                     }
+                };
+
+            for (label, parent, path) in parents? {
+                write_addresses(
+                    &mut output,
+                    &parent,
+                    &path,
+                    label,
+                    range,
+                    script_type,
+                    &args.output,
+                )?;
+            }
+        }
+        Commands::Mnemonic(args) => {
+            let mnemonic = args.mnemonic()?;
+            let mut passphrase = args.passphrase()?;
+            let master = mnemonic.to_master_key(&passphrase);
+            passphrase.zeroize();
+            let master = master?
+                .with_version(args.purpose.version(args.network))
+                .with_network(args.network)?;
+            let path = args.purpose.account_path(args.network, args.account)?;
+            let account = master.derive_account(args.purpose, args.account)?.to_xpub();
+            let origin = account
+                .key_origin()
+                .expect("keys derived from the master key have an origin");
+
+            let record = Record::new()
+                .with(
+                    "master_fingerprint",
+                    format!("{:08x}", master.fingerprint()),
+                )
+                .with("path", path.to_string())
+                .with("xpub", account.to_base58())
+                .with("origin", format!("[{}]", origin));
+            output.write(&record, || {
+                format!(
+                    "Master fingerprint: {:08x}\nAccount {}: {}\nDescriptor key: [{}]{}",
+                    master.fingerprint(),
+                    path,
+                    account.to_base58(),
+                    origin,
+                    account.to_base58()
+                )
+            })?;
+        }
+        Commands::Descriptor(args) => {
+            let descriptor = args.descriptor()?;
+            let script_type = descriptor.script_type();
+
+            // Multipath descriptors label each address with its branch
+            let label_branches = descriptor.is_multipath();
+            let branches = descriptor.branches();
+
+            if descriptor.is_ranged() {
+                let range = index_range(args.start, args.count, args.end)?;
+                output.note(&format!(
+                    "Generating {} addresses for {}:",
+                    range.len() * descriptor.branch_count(),
+                    descriptor
+                ))?;
+                for (branch, descriptor) in branches.iter().enumerate() {
+                    let key = descriptor.key();
+                    write_addresses(
+                        &mut output,
+                        &key.derive_parent()?,
+                        &key.full_path(),
+                        label_branches.then_some(Label::Branch(branch)),
+                        range,
+                        script_type,
+                        &args.output,
+                    )?;
                 }
-                Err(e) => {
-                    eprintln!("Error: {}", e);
+            } else if args.count.is_none() && args.end.is_none() {
+                output.note(&format!("Address for {}:", descriptor))?;
+                for (branch, descriptor) in branches.iter().enumerate() {
+                    let key = descriptor.key();
+                    let xpub = key.derive(0)?;
+                    KeyAddress {
+                        index: None,
+                        label: label_branches.then_some(Label::Branch(branch)),
+                        path: key.full_path(),
+                        key: &xpub,
+                        script_type,
+                        address: &xpub.to_address(script_type),
+                    }
+                    .write(&mut output, &args.output)?;
                 }
+            } else {
+                return Err(Error::InvalidDescriptor(
+                    "COUNT and --end require a descriptor ending with a wildcard".to_string(),
+                )
+                .into());
             }
         }
-        Commands::Bip44(args) => {
+        Commands::Find(args) => {
             let xpub = Xpub::from_base58(&args.extended_public_key)?;
-            println!("Generating {} BIP44 addresses:", args.count);
+            let xpub = match args.network {
+                Some(network) => xpub.with_network(network)?,
+                None => xpub,
+            };
+            let range = args.range()?;
 
-            match xpub.derive_bip44_addresses(args.count) {
-                Ok(addresses) => {
-                    // Print each derived address with its index
-                    for (i, address) in addresses.iter().enumerate() {
-                        println!("Child {}: {}", i, address);
-                    }
+            match xpub.find_address(&args.address, args.change, range)? {
+                Some(found) => {
+                    let record = Record::new()
+                        .with("address", args.address.as_str())
+                        .with("chain", found.chain.name())
+                        .with("index", found.index)
+                        .with("path", found.path().to_relative_string())
+                        .with("script_type", found.script_type.name());
+                    output.write(&record, || format!("Found {} at {}", args.address, found))?;
+                }
+                None => {
+                    let chains: Vec<&str> = args
+                        .change
+                        .chains()
+                        .iter()
+                        .map(|chain| chain.name())
+                        .collect();
+                    eprintln!(
+                        "No match for {} in indices {} of the {} chain{}",
+                        args.address,
+                        range,
+                        chains.join(" and "),
+                        if chains.len() > 1 { "s" } else { "" }
+                    );
+                    output.finish()?;
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Inspect(args) => {
+            let info = KeyInfo::from_base58(&args.extended_public_key)?;
+            output.write(&info.to_record(), || info.to_string())?;
+
+            if !info.checksum_valid {
+                eprintln!("Checksum mismatch: the key is probably mistyped or truncated");
+                output.finish()?;
+                std::process::exit(1);
+            }
+        }
+        Commands::Convert(args) => {
+            let xpub = Xpub::from_base58(&args.extended_public_key)?;
+            let conversion = xpub.convert(args.version);
+            for warning in conversion.warnings() {
+                eprintln!("Warning: {}", warning);
+            }
+
+            let converted = conversion.to_base58();
+            let record = Record::new()
+                .with("from_version", conversion.from.prefix())
+                .with("to_version", conversion.to().prefix())
+                .with("xpub", converted.as_str())
+                .with("script_type", conversion.to().script_type_name())
+                .with("script_type_changed", conversion.changes_script_type())
+                .with("network_changed", conversion.changes_network());
+            output.write(&record, || converted.clone())?;
+        }
+        Commands::Validate(args) => {
+            let parsed = match args.network {
+                Some(network) => Address::parse_for_network(&args.address, network),
+                None => Address::parse(&args.address),
+            };
+
+            let record = Record::new()
+                .with("address", args.address.as_str())
+                .with("valid", parsed.is_ok());
+            match &parsed {
+                Ok(address) => {
+                    let script_pubkey = address.script_pubkey().to_hex();
+                    let record = record
+                        .with("network", address.network().name())
+                        .with("type", address.address_type().name())
+                        .with("witness_version", address.witness_version())
+                        .with("script_pubkey", script_pubkey.as_str())
+                        .with("error", None::<String>);
+                    output.write(&record, || {
+                        let networks: Vec<&str> =
+                            address.networks().iter().map(|n| n.name()).collect();
+                        let mut text = format!(
+                            "Address:         {}\nValid:           yes\nNetwork:         {}\nType:            {}",
+                            args.address,
+                            networks.join(", "),
+                            address.address_type()
+                        );
+                        if let Some(version) = address.witness_version() {
+                            text.push_str(&format!("\nWitness version: {}", version));
+                        }
+                        text.push_str(&format!("\nscriptPubKey:    {}", script_pubkey));
+                        text
+                    })?;
                 }
                 Err(e) => {
-                    eprintln!("Error: {}", e);
+                    let record = record
+                        .with("network", None::<String>)
+                        .with("type", None::<String>)
+                        .with("witness_version", None::<u8>)
+                        .with("script_pubkey", None::<String>)
+                        .with("error", e.to_string());
+                    output.write(&record, || {
+                        format!(
+                            "Address:         {}\nValid:           no\nError:           {}",
+                            args.address, e
+                        )
+                    })?;
+                    output.finish()?;
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Multisig(args) => {
+            let multisig = args.multisig()?;
+            let range = index_range(args.start, args.count, args.end)?;
+
+            output.note(&format!(
+                "Generating {} {}-of-{} {} addresses for {}:",
+                range.len(),
+                multisig.threshold,
+                multisig.xpubs.len(),
+                args.multisig_type,
+                args.path.to_relative_string()
+            ))?;
+            let children = multisig.derive_address_range(&args.path, range, args.multisig_type)?;
+            for (i, child, address) in children {
+                let script = child.script().to_hex();
+                let scripts = if args.script_pubkey {
+                    let mut scripts = vec![(
+                        ScriptColumn::ScriptPubkey,
+                        child.script_pubkey(args.multisig_type),
+                    )];
+                    if let Some(redeem_script) = child.redeem_script(args.multisig_type) {
+                        scripts.push((ScriptColumn::RedeemScript, redeem_script));
+                    }
+                    if let Some(witness_script) = child.witness_script(args.multisig_type) {
+                        scripts.push((ScriptColumn::WitnessScript, witness_script));
+                    }
+                    script_fields(scripts, args.asm)
+                } else {
+                    Vec::new()
+                };
+
+                let mut record = Record::new()
+                    .with("index", i)
+                    .with("path", args.path.child(i).to_relative_string())
+                    .with("script_type", args.multisig_type.name())
+                    .with("address", address.as_str())
+                    .with("script", script.as_str());
+                for (field, _, value) in &scripts {
+                    record = record.with(field, value.as_str());
                 }
+                output.write(&record, || {
+                    let mut text = format!("Child {}: {}\n  Script: {}", i, address, script);
+                    for (_, label, value) in &scripts {
+                        text.push_str(&format!("\n  {}: {}", label, value));
+                    }
+                    text
+                })?;
             }
         }
     }
 
+    output.finish()?;
     Ok(())
 }
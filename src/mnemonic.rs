@@ -0,0 +1,171 @@
+use std::fmt;
+use std::str::FromStr;
+
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroize;
+
+use crate::wordlist::ENGLISH;
+use crate::{hash, Error, Xprv};
+
+/// Number of PBKDF2 rounds used to stretch a mnemonic into a seed (BIP39)
+const PBKDF2_ROUNDS: u32 = 2048;
+
+/// Number of bits encoded by each mnemonic word
+const BITS_PER_WORD: usize = 11;
+
+#[derive(Clone, PartialEq, Eq)]
+/// A BIP39 mnemonic sentence using the English wordlist
+/// Words are stored as wordlist indices, which are wiped from memory when dropped
+pub struct Mnemonic {
+    indices: Vec<u16>,
+}
+
+impl Mnemonic {
+    /// Creates the mnemonic encoding the given entropy (16, 20, 24, 28 or 32 bytes)
+    /// The first entropy_bits / 32 bits of SHA256(entropy) are appended as checksum
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, Error> {
+        if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+            return Err(Error::InvalidEntropyLength(entropy.len()));
+        }
+
+        let checksum = Sha256::digest(entropy);
+        let entropy_bits = entropy.len() * 8;
+        let bit = |i: usize| -> u16 {
+            let (bytes, i) = if i < entropy_bits {
+                (entropy, i)
+            } else {
+                (&checksum[..], i - entropy_bits)
+            };
+            u16::from((bytes[i / 8] >> (7 - i % 8)) & 1)
+        };
+
+        // Each word takes the next 11 bits of entropy || checksum
+        let word_count = entropy.len() * 3 / 4;
+        let indices = (0..word_count)
+            .map(|word| {
+                (0..BITS_PER_WORD).fold(0u16, |index, b| {
+                    (index << 1) | bit(word * BITS_PER_WORD + b)
+                })
+            })
+            .collect();
+
+        Ok(Self { indices })
+    }
+
+    /// Parses a mnemonic sentence, checking every word and the checksum
+    /// Words may be separated by any whitespace and are matched case-insensitively
+    pub fn parse(phrase: &str) -> Result<Self, Error> {
+        let mut normalized: String = phrase.nfkd().collect();
+        let result = Self::parse_normalized(&normalized);
+        normalized.zeroize();
+        result
+    }
+
+    /// Parses an NFKD normalized mnemonic sentence
+    fn parse_normalized(phrase: &str) -> Result<Self, Error> {
+        let words: Vec<&str> = phrase.split_whitespace().collect();
+        if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+            return Err(Error::InvalidWordCount(words.len()));
+        }
+
+        let mut indices = Vec::with_capacity(words.len());
+        for (position, word) in words.iter().enumerate() {
+            let index = ENGLISH
+                .binary_search_by(|probe| {
+                    probe
+                        .bytes()
+                        .cmp(word.bytes().map(|b| b.to_ascii_lowercase()))
+                })
+                .map_err(|_| Error::UnknownWord(position + 1))?;
+            indices.push(index as u16);
+        }
+        let mnemonic = Self { indices };
+
+        // Re-encoding the entropy must reproduce the same checksum bits
+        let mut entropy = mnemonic.to_entropy();
+        let expected = Self::from_entropy(&entropy);
+        entropy.zeroize();
+        if expected? != mnemonic {
+            return Err(Error::InvalidMnemonicChecksum);
+        }
+
+        Ok(mnemonic)
+    }
+
+    /// Number of words in the mnemonic
+    pub fn word_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Iterates over the words of the mnemonic
+    pub fn words(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.indices.iter().map(|index| ENGLISH[*index as usize])
+    }
+
+    /// Returns the entropy encoded by the mnemonic, without the checksum
+    pub fn to_entropy(&self) -> Vec<u8> {
+        let entropy_len = self.indices.len() * 4 / 3;
+        let mut entropy = vec![0u8; entropy_len];
+
+        for (i, index) in self.indices.iter().enumerate() {
+            for b in 0..BITS_PER_WORD {
+                let position = i * BITS_PER_WORD + b;
+                if position >= entropy_len * 8 {
+                    break;
+                }
+                if (index >> (BITS_PER_WORD - 1 - b)) & 1 == 1 {
+                    entropy[position / 8] |= 1 << (7 - position % 8);
+                }
+            }
+        }
+        entropy
+    }
+
+    /// Stretches the mnemonic into a 64 byte seed with PBKDF2-HMAC-SHA512
+    /// The salt is "mnemonic" followed by the NFKD normalized passphrase
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let mut password = self.to_string();
+        let mut salt = String::from("mnemonic");
+        salt.extend(passphrase.nfkd());
+
+        let seed = hash::pbkdf2_hmac_sha512(password.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS);
+        password.zeroize();
+        salt.zeroize();
+        seed
+    }
+
+    /// Derives the BIP32 master key of the mnemonic and passphrase
+    pub fn to_master_key(&self, passphrase: &str) -> Result<Xprv, Error> {
+        let mut seed = self.to_seed(passphrase);
+        let master = Xprv::new_master(&seed);
+        seed.zeroize();
+        master
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, word) in self.words().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(word)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.indices.zeroize();
+    }
+}
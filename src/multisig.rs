@@ -0,0 +1,285 @@
+use std::fmt;
+use std::str::FromStr;
+
+use secp256k1::PublicKey;
+use sha2::{Digest, Sha256};
+
+use crate::script::OP_CHECKMULTISIG;
+use crate::{address, bech32, hash};
+use crate::{DerivationPath, Error, IndexRange, Network, Script, Xpub};
+
+/// Maximum number of keys in a CHECKMULTISIG script
+pub const MAX_PUBKEYS_PER_MULTISIG: usize = 20;
+
+/// Maximum size of a P2SH redeem script, which limits P2SH multisig to 15 keys
+pub const MAX_REDEEM_SCRIPT_SIZE: usize = 520;
+
+/// Address types for multisig scripts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MultisigType {
+    /// Legacy pay to script hash with the multisig script as redeem script (3... / 2...)
+    P2sh,
+    /// Segwit v0 P2WSH nested in pay to script hash (3... / 2...)
+    P2shP2wsh,
+    /// Native segwit v0 pay to witness script hash, bech32 encoded (bc1q...)
+    P2wsh,
+}
+
+impl MultisigType {
+    /// All supported multisig address types
+    pub const ALL: [MultisigType; 3] = [
+        MultisigType::P2sh,
+        MultisigType::P2shP2wsh,
+        MultisigType::P2wsh,
+    ];
+
+    /// Name of the multisig type as accepted by `FromStr`
+    pub fn name(self) -> &'static str {
+        match self {
+            MultisigType::P2sh => "p2sh",
+            MultisigType::P2shP2wsh => "p2sh-p2wsh",
+            MultisigType::P2wsh => "p2wsh",
+        }
+    }
+}
+
+impl fmt::Display for MultisigType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for MultisigType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "p2sh" | "legacy" => Ok(MultisigType::P2sh),
+            "p2sh-p2wsh" | "p2sh-segwit" | "nested" => Ok(MultisigType::P2shP2wsh),
+            "p2wsh" | "segwit" | "bech32" => Ok(MultisigType::P2wsh),
+            _ => Err(Error::UnknownMultisigType(s.to_string())),
+        }
+    }
+}
+
+/// A k-of-n CHECKMULTISIG policy over the extended public keys of its cosigners
+/// With `sorted` set, public keys are sorted as described in BIP67 (sortedmulti);
+/// otherwise they keep the cosigner order (multi)
+#[derive(Clone)]
+pub struct Multisig {
+    pub threshold: usize, // Number of signatures required
+    pub xpubs: Vec<Xpub>, // Extended public keys of the cosigners
+    pub sorted: bool,     // Sort public keys lexicographically (BIP67)
+}
+
+impl Multisig {
+    /// Creates a sorted (BIP67) k-of-n multisig over the given cosigner keys
+    /// Requires 1 <= threshold <= number of keys <= 20 and distinct keys on the same network
+    pub fn new(threshold: usize, xpubs: Vec<Xpub>) -> Result<Self, Error> {
+        if xpubs.is_empty() || xpubs.len() > MAX_PUBKEYS_PER_MULTISIG {
+            return Err(Error::InvalidMultisig(format!(
+                "{} keys given, expected 1 to {}",
+                xpubs.len(),
+                MAX_PUBKEYS_PER_MULTISIG
+            )));
+        }
+        if threshold == 0 || threshold > xpubs.len() {
+            return Err(Error::InvalidMultisig(format!(
+                "threshold {} must be between 1 and the number of keys ({})",
+                threshold,
+                xpubs.len()
+            )));
+        }
+        let network = xpubs[0].network;
+        if let Some(xpub) = xpubs.iter().find(|xpub| xpub.network != network) {
+            return Err(Error::InvalidMultisig(format!(
+                "cosigner keys use different networks ({} and {})",
+                network, xpub.network
+            )));
+        }
+        // A repeated key would let its holder sign more than once
+        let mut keys: Vec<[u8; 33]> = xpubs
+            .iter()
+            .map(|xpub| xpub.public_key.serialize())
+            .collect();
+        keys.sort_unstable();
+        if let Some(pair) = keys.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(Error::InvalidMultisig(format!(
+                "cosigner public key {} is given more than once",
+                hash::hex(&pair[0])
+            )));
+        }
+
+        Ok(Self {
+            threshold,
+            xpubs,
+            sorted: true,
+        })
+    }
+
+    /// Returns a copy of this multisig with BIP67 key sorting enabled or disabled
+    pub fn with_sorted(mut self, sorted: bool) -> Self {
+        self.sorted = sorted;
+        self
+    }
+
+    /// Returns a copy of this multisig with every cosigner key bound to the given network
+    pub fn with_network(mut self, network: Network) -> Result<Self, Error> {
+        self.xpubs = self
+            .xpubs
+            .into_iter()
+            .map(|xpub| xpub.with_network(network))
+            .collect::<Result<_, _>>()?;
+        Ok(self)
+    }
+
+    /// Network used for address generation, shared by all cosigner keys
+    pub fn network(&self) -> Network {
+        self.xpubs[0].network
+    }
+
+    /// Derives the same non-hardened child of every cosigner key
+    pub fn derive_non_hardened(&self, index: u32) -> Result<Self, Error> {
+        self.derive_each(|xpub| xpub.derive_non_hardened(index))
+    }
+
+    /// Derives the same path, relative to each cosigner key, for every cosigner
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Error> {
+        self.derive_each(|xpub| xpub.derive_path(path))
+    }
+
+    /// Applies a derivation to every cosigner key
+    fn derive_each(&self, derive: impl Fn(&Xpub) -> Result<Xpub, Error>) -> Result<Self, Error> {
+        Ok(Self {
+            threshold: self.threshold,
+            xpubs: self.xpubs.iter().map(derive).collect::<Result<_, _>>()?,
+            sorted: self.sorted,
+        })
+    }
+
+    /// Public keys in script order: BIP67 sorted by their compressed serialization,
+    /// or in cosigner order when sorting is disabled
+    pub fn public_keys(&self) -> Vec<PublicKey> {
+        let mut keys: Vec<PublicKey> = self.xpubs.iter().map(|xpub| xpub.public_key).collect();
+        if self.sorted {
+            keys.sort_by_key(|key| key.serialize());
+        }
+        keys
+    }
+
+    /// Builds the multisig script: OP_k <pubkey>... OP_n OP_CHECKMULTISIG
+    /// It is the redeem script of P2SH and the witness script of P2WSH outputs
+    pub fn script(&self) -> Script {
+        let keys = self.public_keys();
+        let mut script = Vec::with_capacity(3 + keys.len() * 34);
+
+        push_small_int(&mut script, self.threshold);
+        for key in &keys {
+            script.push(33); // Push 33 bytes
+            script.extend_from_slice(&key.serialize());
+        }
+        push_small_int(&mut script, keys.len());
+        script.push(OP_CHECKMULTISIG);
+        Script::new(script)
+    }
+
+    /// Generates the address of the multisig script for the requested type
+    /// P2SH fails when the redeem script exceeds 520 bytes (more than 15 keys)
+    pub fn to_address(&self, multisig_type: MultisigType) -> Result<String, Error> {
+        let script = self.script();
+        let network = self.network();
+
+        match multisig_type {
+            MultisigType::P2sh => {
+                if script.len() > MAX_REDEEM_SCRIPT_SIZE {
+                    return Err(Error::InvalidMultisig(format!(
+                        "{} byte redeem script exceeds the P2SH limit of {} bytes",
+                        script.len(),
+                        MAX_REDEEM_SCRIPT_SIZE
+                    )));
+                }
+                Ok(address::base58_address(
+                    network.p2sh_prefix(),
+                    &script.script_hash(),
+                ))
+            }
+            MultisigType::P2shP2wsh => {
+                // Redeem script OP_0 <SHA256(witness_script)>
+                let redeem_script = script.to_p2wsh();
+                Ok(address::base58_address(
+                    network.p2sh_prefix(),
+                    &redeem_script.script_hash(),
+                ))
+            }
+            MultisigType::P2wsh => {
+                bech32::encode_segwit_address(network.bech32_hrp(), 0, &Sha256::digest(&script))
+            }
+        }
+    }
+
+    /// Builds the output script (scriptPubKey) paying to the multisig script
+    /// P2SH and P2SH-P2WSH: OP_HASH160 <HASH160(redeem_script)> OP_EQUAL
+    /// P2WSH: OP_0 <SHA256(witness_script)>
+    pub fn script_pubkey(&self, multisig_type: MultisigType) -> Script {
+        let script = self.script();
+        match multisig_type {
+            MultisigType::P2sh => script.to_p2sh(),
+            MultisigType::P2shP2wsh => script.to_p2wsh().to_p2sh(),
+            MultisigType::P2wsh => script.to_p2wsh(),
+        }
+    }
+
+    /// Returns the redeem script revealed when spending a P2SH output
+    /// P2SH: the multisig script, P2SH-P2WSH: OP_0 <SHA256(witness_script)>
+    pub fn redeem_script(&self, multisig_type: MultisigType) -> Option<Script> {
+        match multisig_type {
+            MultisigType::P2sh => Some(self.script()),
+            MultisigType::P2shP2wsh => Some(self.script().to_p2wsh()),
+            MultisigType::P2wsh => None,
+        }
+    }
+
+    /// Returns the witness script revealed when spending a P2WSH output, which is the
+    /// multisig script for P2WSH and P2SH-P2WSH
+    pub fn witness_script(&self, multisig_type: MultisigType) -> Option<Script> {
+        match multisig_type {
+            MultisigType::P2sh => None,
+            MultisigType::P2shP2wsh | MultisigType::P2wsh => Some(self.script()),
+        }
+    }
+
+    /// Generates multisig addresses for a range of wildcard indices below a derivation path
+    /// Every cosigner key is derived at the same path and index; each address is
+    /// returned with its index and the derived multisig, and indices BIP32 says to skip
+    /// for any cosigner are left out
+    pub fn derive_address_range(
+        &self,
+        path: &DerivationPath,
+        range: IndexRange,
+        multisig_type: MultisigType,
+    ) -> Result<Vec<(u32, Multisig, String)>, Error> {
+        let parent = self.derive_path(&DerivationPath::new(path.steps().to_vec()))?;
+        let mut addresses = Vec::with_capacity(range.len());
+
+        for i in range.iter() {
+            // Indices BIP32 says to skip have no key and no address
+            let child = match parent.derive_non_hardened(i) {
+                Err(Error::InvalidTweak(_)) => continue,
+                child => child?,
+            };
+            let address = child.to_address(multisig_type)?;
+            addresses.push((i, child, address));
+        }
+        Ok(addresses)
+    }
+}
+
+/// Pushes a key count or threshold: OP_1 to OP_16, or a one byte push above 16
+fn push_small_int(script: &mut Vec<u8>, n: usize) {
+    if n <= 16 {
+        script.push(0x50 + n as u8); // OP_n
+    } else {
+        script.push(1); // Push 1 byte
+        script.push(n as u8);
+    }
+}
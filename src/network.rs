@@ -0,0 +1,106 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Bitcoin networks supported for address generation
+/// Each network defines its own Base58 address prefixes and bech32 human readable part
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Network {
+    /// Bitcoin mainnet
+    #[default]
+    Bitcoin,
+    /// Bitcoin testnet (version 3)
+    Testnet,
+    /// Bitcoin testnet (version 4)
+    Testnet4,
+    /// Bitcoin signet
+    Signet,
+    /// Bitcoin regression test network
+    Regtest,
+}
+
+impl Network {
+    /// All supported networks
+    pub const ALL: [Network; 5] = [
+        Network::Bitcoin,
+        Network::Testnet,
+        Network::Testnet4,
+        Network::Signet,
+        Network::Regtest,
+    ];
+
+    /// Returns true for Bitcoin mainnet
+    /// Every other network shares the testnet extended key versions (tpub, upub, vpub)
+    pub fn is_mainnet(self) -> bool {
+        self == Network::Bitcoin
+    }
+
+    /// Version byte prefix for P2PKH addresses (0x00 mainnet, 0x6F otherwise)
+    pub fn p2pkh_prefix(self) -> u8 {
+        if self.is_mainnet() {
+            0x00
+        } else {
+            0x6F
+        }
+    }
+
+    /// Version byte prefix for P2SH addresses (0x05 mainnet, 0xC4 otherwise)
+    pub fn p2sh_prefix(self) -> u8 {
+        if self.is_mainnet() {
+            0x05
+        } else {
+            0xC4
+        }
+    }
+
+    /// SLIP-44 coin type used in account paths (0 mainnet, 1 for every test network)
+    pub fn coin_type(self) -> u32 {
+        if self.is_mainnet() {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Human readable part used by bech32 and bech32m segwit addresses
+    pub fn bech32_hrp(self) -> &'static str {
+        match self {
+            Network::Bitcoin => "bc",
+            Network::Testnet | Network::Testnet4 | Network::Signet => "tb",
+            Network::Regtest => "bcrt",
+        }
+    }
+
+    /// Name of the network as accepted by `FromStr`
+    pub fn name(self) -> &'static str {
+        match self {
+            Network::Bitcoin => "bitcoin",
+            Network::Testnet => "testnet",
+            Network::Testnet4 => "testnet4",
+            Network::Signet => "signet",
+            Network::Regtest => "regtest",
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Network {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bitcoin" | "mainnet" | "main" => Ok(Network::Bitcoin),
+            "testnet" | "testnet3" | "test" => Ok(Network::Testnet),
+            "testnet4" => Ok(Network::Testnet4),
+            "signet" => Ok(Network::Signet),
+            "regtest" => Ok(Network::Regtest),
+            _ => Err(Error::UnknownNetwork(s.to_string())),
+        }
+    }
+}
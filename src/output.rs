@@ -0,0 +1,309 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Output formats for generated records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// Human readable text, phrased by the caller
+    #[default]
+    Text,
+    /// A JSON array of objects
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+    /// Newline delimited JSON: one object per line
+    Ndjson,
+}
+
+impl Format {
+    /// All supported output formats
+    pub const ALL: [Format; 4] = [Format::Text, Format::Json, Format::Csv, Format::Ndjson];
+
+    /// Name of the format as accepted by `FromStr`
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::Ndjson => "ndjson",
+        }
+    }
+
+    /// Returns true for the formats meant to be read by programs
+    pub fn is_machine_readable(self) -> bool {
+        self != Format::Text
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" | "txt" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "ndjson" | "jsonl" => Ok(Format::Ndjson),
+            _ => Err(Error::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+/// Value of a record field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// Missing value: JSON null or an empty CSV cell
+    Null,
+    /// Boolean flag, written as true or false
+    Bool(bool),
+    /// Unsigned integer, written without quotes in JSON
+    Number(u64),
+    /// Text, quoted and escaped as each format requires
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => Ok(()),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(n: u32) -> Self {
+        Value::Number(n.into())
+    }
+}
+
+impl From<u8> for Value {
+    fn from(n: u8) -> Self {
+        Value::Number(n.into())
+    }
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Value::Number(n as u64)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Text(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Text(s.to_string())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+/// An ordered list of named fields describing one output item, such as a derived address
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Record {
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl Record {
+    /// Creates a record without fields
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns this record with a field appended
+    pub fn with(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+        self.fields.push((name, value.into()));
+        self
+    }
+
+    /// Fields of the record in insertion order
+    pub fn fields(&self) -> &[(&'static str, Value)] {
+        &self.fields
+    }
+
+    /// Value of the named field, if present
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Serializes the record as a single line JSON object
+    pub fn to_json(&self) -> String {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), json_value(value)))
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+/// Writes records in the requested format
+/// Machine readable formats serialize the records, while the text format writes the
+/// line phrased by the caller for each record. Notes such as headers go to the output
+/// in the text format and to the notes writer otherwise, keeping machine output clean
+pub struct RecordWriter<W: Write, N: Write = io::Sink> {
+    out: W,                     // Destination of the records
+    notes: N,                   // Destination of the notes in machine readable formats
+    format: Format,             // Output format
+    count: usize,               // Number of records written so far
+    columns: Vec<&'static str>, // CSV columns, taken from the first record
+}
+
+impl<W: Write> RecordWriter<W> {
+    /// Creates a writer for the given output and format
+    /// Notes are discarded in machine readable formats until a notes writer is set
+    pub fn new(out: W, format: Format) -> Self {
+        Self {
+            out,
+            notes: io::sink(),
+            format,
+            count: 0,
+            columns: Vec::new(),
+        }
+    }
+}
+
+impl<W: Write, N: Write> RecordWriter<W, N> {
+    /// Returns this writer sending the notes of machine readable formats to `notes`,
+    /// such as standard error
+    pub fn with_notes<M: Write>(self, notes: M) -> RecordWriter<W, M> {
+        RecordWriter {
+            out: self.out,
+            notes,
+            format: self.format,
+            count: self.count,
+            columns: self.columns,
+        }
+    }
+
+    /// Output format of the writer
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Number of records written so far
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Writes a note that is not part of the records, such as a header line
+    pub fn note(&mut self, text: &str) -> io::Result<()> {
+        if self.format.is_machine_readable() {
+            writeln!(self.notes, "{}", text)
+        } else {
+            writeln!(self.out, "{}", text)
+        }
+    }
+
+    /// Writes a record; `text` phrases it for the text format and is only called there
+    pub fn write(&mut self, record: &Record, text: impl FnOnce() -> String) -> io::Result<()> {
+        match self.format {
+            Format::Text => writeln!(self.out, "{}", text())?,
+            Format::Json => {
+                let separator = if self.count == 0 { "[\n" } else { ",\n" };
+                write!(self.out, "{}  {}", separator, record.to_json())?;
+            }
+            Format::Ndjson => writeln!(self.out, "{}", record.to_json())?,
+            Format::Csv => {
+                if self.count == 0 {
+                    self.columns = record.fields().iter().map(|(name, _)| *name).collect();
+                    let header: Vec<String> = self.columns.iter().map(|c| csv_cell(c)).collect();
+                    writeln!(self.out, "{}", header.join(","))?;
+                }
+                // Later records are written in the columns of the first one
+                let row: Vec<String> = self
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        record
+                            .get(column)
+                            .map_or_else(String::new, |value| csv_cell(&value.to_string()))
+                    })
+                    .collect();
+                writeln!(self.out, "{}", row.join(","))?;
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Completes the output, closing the JSON array, and flushes it
+    pub fn finish(mut self) -> io::Result<()> {
+        if self.format == Format::Json {
+            if self.count == 0 {
+                writeln!(self.out, "[]")?;
+            } else {
+                writeln!(self.out, "\n]")?;
+            }
+        }
+        self.notes.flush()?;
+        self.out.flush()
+    }
+}
+
+/// Formats a value as JSON
+fn json_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Text(s) => json_string(s),
+    }
+}
+
+/// Quotes and escapes a JSON string
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Quotes a CSV cell when it contains a separator, quote or line break (RFC 4180)
+fn csv_cell(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
@@ -0,0 +1,82 @@
+use rayon::prelude::*;
+use secp256k1::Verification;
+
+use crate::engine::{self, Engine};
+use crate::{Chain, ChainSelection, Error, IndexRange, ScriptType, Xpub};
+
+impl<C: Verification> Engine<C> {
+    /// Derives the children of `xpub` in the range across the threads of the current
+    /// rayon pool, returning (index, address) pairs in index order
+    /// The output is identical to the sequential derivation, including skipped indices
+    pub fn par_address_range(
+        &self,
+        xpub: &Xpub,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Vec<(u32, String)>, Error> {
+        Ok(self
+            .par_children(xpub, range, script_type)?
+            .into_iter()
+            .map(|(index, _, address)| (index, address))
+            .collect())
+    }
+
+    /// Parallel counterpart of `address_iter`, collecting (index, child Xpub, address)
+    /// in index order on the current rayon pool
+    pub fn par_children(
+        &self,
+        xpub: &Xpub,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Vec<(u32, Xpub, String)>, Error> {
+        // Checked once here, so the only derivation error left is an invalid tweak
+        xpub.depth.checked_add(1).ok_or(Error::DepthOverflow)?;
+        let fingerprint = xpub.fingerprint();
+        let (start, step) = (range.start(), range.step());
+
+        Ok((0..range.len() as u32)
+            .into_par_iter()
+            .map(|position| start + position * step)
+            .filter_map(|index| {
+                // Indices BIP32 says to skip have no key and no address
+                let child = self.derive_child(xpub, fingerprint, index).ok()?;
+                let address = self.address(&child, script_type);
+                Some((index, child, address))
+            })
+            .collect())
+    }
+}
+
+impl Xpub {
+    /// Parallel version of `derive_bip32_address_range`, using the current rayon pool
+    /// (the global pool unless called inside `ThreadPool::install`)
+    pub fn par_derive_bip32_address_range(
+        &self,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Vec<(u32, String)>, Error> {
+        engine::global().par_address_range(self, range, script_type)
+    }
+
+    /// Parallel version of `derive_bip44_address_range`; each chain is split across
+    /// the threads in turn, so receive addresses still come before change addresses
+    pub fn par_derive_bip44_address_range(
+        &self,
+        selection: ChainSelection,
+        range: IndexRange,
+        script_type: ScriptType,
+    ) -> Result<Vec<(Chain, u32, String)>, Error> {
+        let mut addresses = Vec::with_capacity(range.len() * selection.chains().len());
+
+        for chain in selection.chains() {
+            let chain_key = self.derive_non_hardened(chain.index())?;
+            let chain_addresses = chain_key.par_derive_bip32_address_range(range, script_type)?;
+            addresses.extend(
+                chain_addresses
+                    .into_iter()
+                    .map(|(i, address)| (*chain, i, address)),
+            );
+        }
+        Ok(addresses)
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// First hardened child index (2^31)
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// A BIP32 derivation path such as "m/44'/0'/0'/0/5" or "0/*"
+/// Steps store the raw child number, with hardened steps offset by 2^31
+/// A trailing wildcard ("*") stands for the index of generated addresses
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DerivationPath {
+    steps: Vec<u32>,
+    wildcard: bool,
+}
+
+impl DerivationPath {
+    /// Creates a path from raw child numbers
+    pub fn new(steps: Vec<u32>) -> Self {
+        Self {
+            steps,
+            wildcard: false,
+        }
+    }
+
+    /// Returns a copy of this path ending with a wildcard step
+    pub fn with_wildcard(mut self) -> Self {
+        self.wildcard = true;
+        self
+    }
+
+    /// Raw child numbers of the path, excluding the wildcard
+    pub fn steps(&self) -> &[u32] {
+        &self.steps
+    }
+
+    /// Returns true if the path ends with a wildcard step
+    pub fn has_wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    /// Returns true if the path has no steps (and no wildcard)
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty() && !self.wildcard
+    }
+
+    /// Returns a new path with the given child number appended
+    /// A wildcard, if present, is replaced by the child number
+    pub fn child(&self, index: u32) -> Self {
+        let mut steps = self.steps.clone();
+        steps.push(index);
+        Self::new(steps)
+    }
+
+    /// Formats the path without the leading "m", e.g. "0/5" or "1/*", for paths relative
+    /// to a key other than the master key
+    pub fn to_relative_string(&self) -> String {
+        let mut steps: Vec<String> = self.steps.iter().map(|step| format_step(*step)).collect();
+        if self.wildcard {
+            steps.push("*".to_string());
+        }
+        steps.join("/")
+    }
+
+    /// Returns the first hardened step and its position, if any
+    pub fn first_hardened(&self) -> Option<(usize, u32)> {
+        self.steps
+            .iter()
+            .enumerate()
+            .find(|(_, step)| is_hardened(**step))
+            .map(|(position, step)| (position, *step))
+    }
+}
+
+/// Returns true if the child number is in the hardened range
+pub fn is_hardened(index: u32) -> bool {
+    index >= HARDENED_OFFSET
+}
+
+/// Formats a child number as a path step, marking hardened steps with '
+pub fn format_step(index: u32) -> String {
+    if is_hardened(index) {
+        format!("{}'", index - HARDENED_OFFSET)
+    } else {
+        index.to_string()
+    }
+}
+
+/// Parses a single path step such as "5", "44'" or "44h"
+pub(crate) fn parse_step(step: &str) -> Result<u32, Error> {
+    let (number, hardened) = match step.strip_suffix(['\'', 'h', 'H']) {
+        Some(number) => (number, true),
+        None => (step, false),
+    };
+
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::InvalidPath(format!("invalid step {}", step)));
+    }
+
+    let index: u32 = number
+        .parse()
+        .map_err(|_| Error::InvalidPath(format!("step {} out of range", step)))?;
+    if is_hardened(index) {
+        return Err(Error::InvalidPath(format!("step {} out of range", step)));
+    }
+
+    Ok(if hardened {
+        index + HARDENED_OFFSET
+    } else {
+        index
+    })
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    /// Parses a path with an optional leading "m", using ' or h as hardened markers
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = match s {
+            "" | "m" => Vec::new(),
+            _ => s.split('/').collect(),
+        };
+        if parts.first() == Some(&"m") {
+            parts.remove(0);
+        }
+
+        let mut path = DerivationPath::default();
+        for (position, part) in parts.iter().enumerate() {
+            if path.wildcard {
+                return Err(Error::InvalidPath(format!(
+                    "wildcard must be the last step of {}",
+                    s
+                )));
+            }
+            match *part {
+                "*" => path.wildcard = true,
+                "*'" | "*h" | "*H" => {
+                    return Err(Error::InvalidPath(format!(
+                        "hardened wildcard is not supported at step {}",
+                        position + 1
+                    )))
+                }
+                _ => path.steps.push(parse_step(part)?),
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            f.write_str("m")
+        } else {
+            write!(f, "m/{}", self.to_relative_string())
+        }
+    }
+}
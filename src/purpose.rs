@@ -0,0 +1,108 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::path::HARDENED_OFFSET;
+use crate::{DerivationPath, Error, Network, ScriptType, Version};
+
+/// BIP43 purpose of a single key account, selecting its address scheme
+/// Accounts follow m/purpose'/coin_type'/account'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Purpose {
+    /// Legacy P2PKH accounts (BIP44)
+    #[default]
+    Bip44,
+    /// Nested segwit P2SH-P2WPKH accounts (BIP49)
+    Bip49,
+    /// Native segwit P2WPKH accounts (BIP84)
+    Bip84,
+    /// Taproot P2TR accounts (BIP86)
+    Bip86,
+}
+
+impl Purpose {
+    /// All supported purposes
+    pub const ALL: [Purpose; 4] = [
+        Purpose::Bip44,
+        Purpose::Bip49,
+        Purpose::Bip84,
+        Purpose::Bip86,
+    ];
+
+    /// Purpose number used as the first (hardened) path step
+    pub fn number(self) -> u32 {
+        match self {
+            Purpose::Bip44 => 44,
+            Purpose::Bip49 => 49,
+            Purpose::Bip84 => 84,
+            Purpose::Bip86 => 86,
+        }
+    }
+
+    /// Script type of the addresses generated by accounts with this purpose
+    pub fn script_type(self) -> ScriptType {
+        match self {
+            Purpose::Bip44 => ScriptType::P2pkh,
+            Purpose::Bip49 => ScriptType::P2shP2wpkh,
+            Purpose::Bip84 => ScriptType::P2wpkh,
+            Purpose::Bip86 => ScriptType::P2tr,
+        }
+    }
+
+    /// SLIP-132 version used to serialize account keys on the given network
+    /// BIP86 has no registered version, so Taproot accounts use xpub/tpub
+    pub fn version(self, network: Network) -> Version {
+        match (self, network.is_mainnet()) {
+            (Purpose::Bip44 | Purpose::Bip86, true) => Version::Xpub,
+            (Purpose::Bip44 | Purpose::Bip86, false) => Version::Tpub,
+            (Purpose::Bip49, true) => Version::Ypub,
+            (Purpose::Bip49, false) => Version::Upub,
+            (Purpose::Bip84, true) => Version::Zpub,
+            (Purpose::Bip84, false) => Version::Vpub,
+        }
+    }
+
+    /// Returns the account path m/purpose'/coin_type'/account' for the given network
+    pub fn account_path(self, network: Network, account: u32) -> Result<DerivationPath, Error> {
+        if account >= HARDENED_OFFSET {
+            return Err(Error::InvalidPath(format!(
+                "account {} must be below 2^31",
+                account
+            )));
+        }
+        Ok(DerivationPath::new(vec![
+            self.number() + HARDENED_OFFSET,
+            network.coin_type() + HARDENED_OFFSET,
+            account + HARDENED_OFFSET,
+        ]))
+    }
+
+    /// Name of the purpose as accepted by `FromStr`
+    pub fn name(self) -> &'static str {
+        match self {
+            Purpose::Bip44 => "bip44",
+            Purpose::Bip49 => "bip49",
+            Purpose::Bip84 => "bip84",
+            Purpose::Bip86 => "bip86",
+        }
+    }
+}
+
+impl fmt::Display for Purpose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Purpose {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "44" | "bip44" => Ok(Purpose::Bip44),
+            "49" | "bip49" => Ok(Purpose::Bip49),
+            "84" | "bip84" => Ok(Purpose::Bip84),
+            "86" | "bip86" => Ok(Purpose::Bip86),
+            _ => Err(Error::UnknownPurpose(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+
+use crate::path::HARDENED_OFFSET;
+use crate::Error;
+
+/// A range of non-hardened child indices used for address derivation
+/// The range is half open (start..end) and never crosses the hardened boundary at 2^31
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IndexRange {
+    start: u32,
+    end: u32,
+    step: u32,
+}
+
+impl IndexRange {
+    /// Creates the range start..end with a step of 1
+    pub fn new(start: u32, end: u32) -> Result<Self, Error> {
+        if start > end {
+            return Err(Error::RangeStartAfterEnd { start, end });
+        }
+        if end > HARDENED_OFFSET {
+            return Err(Error::RangeCrossesHardened { end });
+        }
+        Ok(Self {
+            start,
+            end,
+            step: 1,
+        })
+    }
+
+    /// Creates the range of `count` indices starting at `start`
+    pub fn from_count(start: u32, count: u32) -> Result<Self, Error> {
+        let end = start
+            .checked_add(count)
+            .ok_or(Error::RangeOverflow { start, count })?;
+        Self::new(start, end)
+    }
+
+    /// Returns a copy of this range visiting every `step`-th index
+    pub fn with_step(mut self, step: u32) -> Result<Self, Error> {
+        if step == 0 {
+            return Err(Error::ZeroRangeStep);
+        }
+        self.step = step;
+        Ok(self)
+    }
+
+    /// First index of the range
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Exclusive upper bound of the range
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Distance between consecutive indices
+    pub fn step(&self) -> u32 {
+        self.step
+    }
+
+    /// Number of indices in the range
+    pub fn len(&self) -> usize {
+        ((self.end - self.start).div_ceil(self.step)) as usize
+    }
+
+    /// Returns true if the range contains no indices
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Iterates over the indices of the range
+    pub fn iter(&self) -> impl Iterator<Item = u32> {
+        (self.start..self.end).step_by(self.step as usize)
+    }
+
+    /// Splits the range into consecutive ranges of at most `size` indices (at least 1)
+    /// with the same step, so long ranges can be processed piece by piece in order
+    pub fn chunks(&self, size: u32) -> impl Iterator<Item = IndexRange> {
+        let (end, step) = (self.end, self.step);
+        let span = size.max(1).saturating_mul(step);
+        (self.start..self.end)
+            .step_by(span as usize)
+            .map(move |start| IndexRange {
+                start,
+                end: start.saturating_add(span).min(end),
+                step,
+            })
+    }
+}
+
+impl fmt::Display for IndexRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)?;
+        if self.step != 1 {
+            write!(f, " (step {})", self.step)?;
+        }
+        Ok(())
+    }
+}
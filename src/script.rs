@@ -0,0 +1,241 @@
+use std::fmt;
+use std::ops::Deref;
+
+use sha2::{Digest, Sha256};
+
+use crate::hash;
+
+// Opcodes used by the standard output, redeem and witness scripts
+const OP_0: u8 = 0x00;
+const OP_PUSHDATA1: u8 = 0x4c;
+const OP_PUSHDATA2: u8 = 0x4d;
+const OP_PUSHDATA4: u8 = 0x4e;
+const OP_1NEGATE: u8 = 0x4f;
+const OP_1: u8 = 0x51;
+const OP_16: u8 = 0x60;
+const OP_DUP: u8 = 0x76;
+const OP_EQUAL: u8 = 0x87;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_HASH160: u8 = 0xa9;
+const OP_CHECKSIG: u8 = 0xac;
+pub(crate) const OP_CHECKMULTISIG: u8 = 0xae;
+
+/// A serialized Bitcoin script, such as a scriptPubKey, redeem script or witness script
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Script(Vec<u8>);
+
+impl Script {
+    /// Wraps serialized script bytes
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Builds a P2PKH output script: OP_DUP OP_HASH160 <pubkey_hash> OP_EQUALVERIFY OP_CHECKSIG
+    pub fn p2pkh(pubkey_hash: &[u8; 20]) -> Self {
+        let mut script = Vec::with_capacity(25);
+        script.extend_from_slice(&[OP_DUP, OP_HASH160, 20]);
+        script.extend_from_slice(pubkey_hash);
+        script.extend_from_slice(&[OP_EQUALVERIFY, OP_CHECKSIG]);
+        Self(script)
+    }
+
+    /// Builds a P2SH output script: OP_HASH160 <script_hash> OP_EQUAL
+    pub fn p2sh(script_hash: &[u8; 20]) -> Self {
+        let mut script = Vec::with_capacity(23);
+        script.extend_from_slice(&[OP_HASH160, 20]);
+        script.extend_from_slice(script_hash);
+        script.push(OP_EQUAL);
+        Self(script)
+    }
+
+    /// Builds a segwit output script: OP_n <program>, with OP_0 for version 0
+    /// The version (0 to 16) and program length (2 to 40 bytes) are not checked
+    pub fn witness_program(version: u8, program: &[u8]) -> Self {
+        let opcode = if version == 0 {
+            OP_0
+        } else {
+            OP_1 - 1 + version
+        };
+        let mut script = Vec::with_capacity(2 + program.len());
+        script.extend_from_slice(&[opcode, program.len() as u8]);
+        script.extend_from_slice(program);
+        Self(script)
+    }
+
+    /// Builds a P2WPKH output script: OP_0 <pubkey_hash>
+    pub fn p2wpkh(pubkey_hash: &[u8; 20]) -> Self {
+        Self::witness_program(0, pubkey_hash)
+    }
+
+    /// Builds a P2TR output script: OP_1 <output_key>
+    pub fn p2tr(output_key: &[u8; 32]) -> Self {
+        Self::witness_program(1, output_key)
+    }
+
+    /// HASH160 of this script, the hash a P2SH output commits to
+    pub fn script_hash(&self) -> [u8; 20] {
+        hash::hash160(&self.0)
+    }
+
+    /// P2SH output script paying to HASH160 of this script, used as its redeem script
+    pub fn to_p2sh(&self) -> Self {
+        Self::p2sh(&self.script_hash())
+    }
+
+    /// P2WSH output script paying to SHA256 of this script, used as its witness script
+    pub fn to_p2wsh(&self) -> Self {
+        Self::witness_program(0, &Sha256::digest(&self.0))
+    }
+
+    /// Serialized script bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consumes the script and returns its bytes
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Formats the script bytes as lowercase hex
+    pub fn to_hex(&self) -> String {
+        hash::hex(&self.0)
+    }
+
+    /// Renders the script as assembly in the style of Bitcoin Core's decodescript
+    /// Small integers are written as numbers, pushed data as hex and other opcodes by
+    /// name, e.g. "OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY OP_CHECKSIG" or "0 <program>"
+    /// A push running past the end of the script is rendered as "[error]"
+    pub fn to_asm(&self) -> String {
+        let mut tokens = Vec::new();
+        let mut rest = self.0.as_slice();
+
+        while let Some((&opcode, tail)) = rest.split_first() {
+            rest = tail;
+            let push_len = match opcode {
+                0x01..=0x4b => Some(opcode as usize),
+                OP_PUSHDATA1 => read_length(&mut rest, 1),
+                OP_PUSHDATA2 => read_length(&mut rest, 2),
+                OP_PUSHDATA4 => read_length(&mut rest, 4),
+                _ => {
+                    tokens.push(opcode_name(opcode));
+                    continue;
+                }
+            };
+
+            match push_len {
+                Some(len) if len <= rest.len() => {
+                    let (data, tail) = rest.split_at(len);
+                    tokens.push(hash::hex(data));
+                    rest = tail;
+                }
+                _ => {
+                    tokens.push("[error]".to_string());
+                    break;
+                }
+            }
+        }
+
+        tokens.join(" ")
+    }
+}
+
+impl Deref for Script {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Script {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Script {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Script> for Vec<u8> {
+    fn from(script: Script) -> Self {
+        script.0
+    }
+}
+
+impl fmt::Display for Script {
+    /// Formats the script as hex
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// Reads the little endian length of an OP_PUSHDATA1/2/4 push
+fn read_length(script: &mut &[u8], size: usize) -> Option<usize> {
+    if script.len() < size {
+        return None;
+    }
+    let (bytes, rest) = script.split_at(size);
+    *script = rest;
+    Some(
+        bytes
+            .iter()
+            .rev()
+            .fold(0usize, |len, byte| (len << 8) | *byte as usize),
+    )
+}
+
+/// Assembly name of a non-push opcode
+fn opcode_name(opcode: u8) -> String {
+    let name = match opcode {
+        OP_0 => "0",
+        OP_1NEGATE => "-1",
+        OP_1..=OP_16 => return (opcode - OP_1 + 1).to_string(),
+        0x50 => "OP_RESERVED",
+        0x61 => "OP_NOP",
+        0x63 => "OP_IF",
+        0x64 => "OP_NOTIF",
+        0x67 => "OP_ELSE",
+        0x68 => "OP_ENDIF",
+        0x69 => "OP_VERIFY",
+        0x6a => "OP_RETURN",
+        0x6b => "OP_TOALTSTACK",
+        0x6c => "OP_FROMALTSTACK",
+        0x73 => "OP_IFDUP",
+        0x74 => "OP_DEPTH",
+        0x75 => "OP_DROP",
+        OP_DUP => "OP_DUP",
+        0x77 => "OP_NIP",
+        0x78 => "OP_OVER",
+        0x7c => "OP_SWAP",
+        0x82 => "OP_SIZE",
+        OP_EQUAL => "OP_EQUAL",
+        OP_EQUALVERIFY => "OP_EQUALVERIFY",
+        0x8b => "OP_1ADD",
+        0x8c => "OP_1SUB",
+        0x91 => "OP_NOT",
+        0x93 => "OP_ADD",
+        0x94 => "OP_SUB",
+        0x9a => "OP_BOOLAND",
+        0x9c => "OP_NUMEQUAL",
+        0x9d => "OP_NUMEQUALVERIFY",
+        0xa6 => "OP_RIPEMD160",
+        0xa7 => "OP_SHA1",
+        0xa8 => "OP_SHA256",
+        OP_HASH160 => "OP_HASH160",
+        0xaa => "OP_HASH256",
+        0xab => "OP_CODESEPARATOR",
+        OP_CHECKSIG => "OP_CHECKSIG",
+        0xad => "OP_CHECKSIGVERIFY",
+        OP_CHECKMULTISIG => "OP_CHECKMULTISIG",
+        0xaf => "OP_CHECKMULTISIGVERIFY",
+        0xb1 => "OP_CHECKLOCKTIMEVERIFY",
+        0xb2 => "OP_CHECKSEQUENCEVERIFY",
+        0xba => "OP_CHECKSIGADD",
+        _ => "OP_UNKNOWN",
+    };
+    name.to_string()
+}
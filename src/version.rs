@@ -0,0 +1,235 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, MultisigType, Network, ScriptType};
+
+/// Version bytes of a serialized extended public key, as registered in SLIP-132
+/// The version identifies both the network class (mainnet or test networks)
+/// and the script type the key is intended to be used with
+/// Each version has a private counterpart (e.g. xprv for xpub) used by `Xprv`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Version {
+    /// Mainnet P2PKH or P2SH (0x0488B21E)
+    Xpub,
+    /// Mainnet P2WPKH nested in P2SH (0x049D7CB2)
+    Ypub,
+    /// Mainnet P2WSH multisig nested in P2SH (0x0295B43F)
+    YpubMultisig,
+    /// Mainnet native P2WPKH (0x04B24746)
+    Zpub,
+    /// Mainnet native P2WSH multisig (0x02AA7ED3)
+    ZpubMultisig,
+    /// Testnet P2PKH or P2SH (0x043587CF)
+    Tpub,
+    /// Testnet P2WPKH nested in P2SH (0x044A5262)
+    Upub,
+    /// Testnet P2WSH multisig nested in P2SH (0x024289EF)
+    UpubMultisig,
+    /// Testnet native P2WPKH (0x045F1CF6)
+    Vpub,
+    /// Testnet native P2WSH multisig (0x02575483)
+    VpubMultisig,
+}
+
+impl Version {
+    /// All known public key versions
+    pub const ALL: [Version; 10] = [
+        Version::Xpub,
+        Version::Ypub,
+        Version::YpubMultisig,
+        Version::Zpub,
+        Version::ZpubMultisig,
+        Version::Tpub,
+        Version::Upub,
+        Version::UpubMultisig,
+        Version::Vpub,
+        Version::VpubMultisig,
+    ];
+
+    /// Returns the 4 version bytes used in the serialized key
+    pub fn to_bytes(self) -> [u8; 4] {
+        match self {
+            Version::Xpub => [0x04, 0x88, 0xB2, 0x1E],
+            Version::Ypub => [0x04, 0x9D, 0x7C, 0xB2],
+            Version::YpubMultisig => [0x02, 0x95, 0xB4, 0x3F],
+            Version::Zpub => [0x04, 0xB2, 0x47, 0x46],
+            Version::ZpubMultisig => [0x02, 0xAA, 0x7E, 0xD3],
+            Version::Tpub => [0x04, 0x35, 0x87, 0xCF],
+            Version::Upub => [0x04, 0x4A, 0x52, 0x62],
+            Version::UpubMultisig => [0x02, 0x42, 0x89, 0xEF],
+            Version::Vpub => [0x04, 0x5F, 0x1C, 0xF6],
+            Version::VpubMultisig => [0x02, 0x57, 0x54, 0x83],
+        }
+    }
+
+    /// Returns the 4 version bytes used in the serialized private key
+    pub fn to_private_bytes(self) -> [u8; 4] {
+        match self {
+            Version::Xpub => [0x04, 0x88, 0xAD, 0xE4],
+            Version::Ypub => [0x04, 0x9D, 0x78, 0x78],
+            Version::YpubMultisig => [0x02, 0x95, 0xB0, 0x05],
+            Version::Zpub => [0x04, 0xB2, 0x43, 0x0C],
+            Version::ZpubMultisig => [0x02, 0xAA, 0x7A, 0x99],
+            Version::Tpub => [0x04, 0x35, 0x83, 0x94],
+            Version::Upub => [0x04, 0x4A, 0x4E, 0x28],
+            Version::UpubMultisig => [0x02, 0x42, 0x85, 0xB5],
+            Version::Vpub => [0x04, 0x5F, 0x18, 0xBC],
+            Version::VpubMultisig => [0x02, 0x57, 0x50, 0x48],
+        }
+    }
+
+    /// Looks up the version matching the given bytes
+    /// Private key versions and unknown bytes are rejected
+    pub fn from_bytes(bytes: [u8; 4]) -> Result<Self, Error> {
+        if let Some(version) = Self::ALL.iter().find(|v| v.to_bytes() == bytes) {
+            return Ok(*version);
+        }
+
+        if let Some(version) = Self::ALL.iter().find(|v| v.to_private_bytes() == bytes) {
+            return Err(Error::PrivateKeyVersion(version.private_prefix()));
+        }
+
+        Err(Error::UnknownVersion(bytes))
+    }
+
+    /// Looks up the version whose private counterpart matches the given bytes
+    /// Public key versions and unknown bytes are rejected
+    pub fn from_private_bytes(bytes: [u8; 4]) -> Result<Self, Error> {
+        if let Some(version) = Self::ALL.iter().find(|v| v.to_private_bytes() == bytes) {
+            return Ok(*version);
+        }
+
+        if let Some(version) = Self::ALL.iter().find(|v| v.to_bytes() == bytes) {
+            return Err(Error::PublicKeyVersion(version.prefix()));
+        }
+
+        Err(Error::UnknownVersion(bytes))
+    }
+
+    /// Human readable prefix of the Base58 encoded key (e.g. "xpub", "Zpub")
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Version::Xpub => "xpub",
+            Version::Ypub => "ypub",
+            Version::YpubMultisig => "Ypub",
+            Version::Zpub => "zpub",
+            Version::ZpubMultisig => "Zpub",
+            Version::Tpub => "tpub",
+            Version::Upub => "upub",
+            Version::UpubMultisig => "Upub",
+            Version::Vpub => "vpub",
+            Version::VpubMultisig => "Vpub",
+        }
+    }
+
+    /// Human readable prefix of the Base58 encoded private key (e.g. "xprv", "Zprv")
+    pub fn private_prefix(self) -> &'static str {
+        match self {
+            Version::Xpub => "xprv",
+            Version::Ypub => "yprv",
+            Version::YpubMultisig => "Yprv",
+            Version::Zpub => "zprv",
+            Version::ZpubMultisig => "Zprv",
+            Version::Tpub => "tprv",
+            Version::Upub => "uprv",
+            Version::UpubMultisig => "Uprv",
+            Version::Vpub => "vprv",
+            Version::VpubMultisig => "Vprv",
+        }
+    }
+
+    /// Returns true for versions used on Bitcoin mainnet
+    pub fn is_mainnet(self) -> bool {
+        matches!(
+            self,
+            Version::Xpub
+                | Version::Ypub
+                | Version::YpubMultisig
+                | Version::Zpub
+                | Version::ZpubMultisig
+        )
+    }
+
+    /// Network assumed for a freshly parsed key with this version
+    /// Test versions cannot distinguish testnet, signet and regtest, so testnet is assumed
+    pub fn default_network(self) -> Network {
+        if self.is_mainnet() {
+            Network::Bitcoin
+        } else {
+            Network::Testnet
+        }
+    }
+
+    /// Single key script type implied by the version (SLIP-132)
+    /// Multisig versions map to the single key type with the same wrapping
+    pub fn script_type(self) -> ScriptType {
+        match self {
+            Version::Xpub | Version::Tpub => ScriptType::P2pkh,
+            Version::Ypub | Version::YpubMultisig | Version::Upub | Version::UpubMultisig => {
+                ScriptType::P2shP2wpkh
+            }
+            Version::Zpub | Version::ZpubMultisig | Version::Vpub | Version::VpubMultisig => {
+                ScriptType::P2wpkh
+            }
+        }
+    }
+
+    /// Script type of single key addresses generated from a key with this version
+    /// Multisig versions belong to a multisig wallet, whose addresses single key
+    /// generation cannot produce, so they return `Error::MultisigVersion`
+    pub fn single_key_script_type(self) -> Result<ScriptType, Error> {
+        if self.is_multisig() {
+            return Err(Error::MultisigVersion(self));
+        }
+        Ok(self.script_type())
+    }
+
+    /// Returns true for the multisig (capitalized) SLIP-132 variants
+    pub fn is_multisig(self) -> bool {
+        matches!(
+            self,
+            Version::YpubMultisig
+                | Version::ZpubMultisig
+                | Version::UpubMultisig
+                | Version::VpubMultisig
+        )
+    }
+
+    /// Name of the script type implied by the version, the multisig one for
+    /// multisig variants (e.g. "p2wpkh" for zpub, "p2wsh" for Zpub)
+    pub fn script_type_name(self) -> &'static str {
+        match self.multisig_type() {
+            Some(multisig_type) => multisig_type.name(),
+            None => self.script_type().name(),
+        }
+    }
+
+    /// Multisig script type implied by the multisig variants (SLIP-132)
+    pub fn multisig_type(self) -> Option<MultisigType> {
+        match self {
+            Version::YpubMultisig | Version::UpubMultisig => Some(MultisigType::P2shP2wsh),
+            Version::ZpubMultisig | Version::VpubMultisig => Some(MultisigType::P2wsh),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.prefix())
+    }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    /// Parses a key prefix such as "xpub" or "Zpub"
+    /// Prefixes are case sensitive, since "zpub" and "Zpub" are different versions
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|version| version.prefix() == s)
+            .copied()
+            .ok_or_else(|| Error::UnknownVersionPrefix(s.to_string()))
+    }
+}
@@ -0,0 +1,290 @@
+use base58::FromBase58;
+use hmac::{Hmac, Mac};
+use secp256k1::{PublicKey, Scalar, SecretKey};
+use sha2::Sha512;
+use zeroize::Zeroize;
+
+use crate::{base58check, check_master_fields, engine, hash, path};
+use crate::{DerivationPath, Error, KeyOrigin, Network, Purpose, Version, Xpub};
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// HMAC key used to derive the master key from a seed (BIP32)
+const MASTER_KEY_SALT: &[u8] = b"Bitcoin seed";
+
+#[derive(Clone)]
+/// Represents an extended private key (xprv) following the BIP32 specification
+/// The secret key and chain code are wiped from memory when the key is dropped
+pub struct Xprv {
+    pub version: Version,          // SLIP-132 version of the matching public key
+    pub network: Network,          // Network used for address generation
+    pub depth: u8,                 // Depth in the HD tree
+    pub parent_fingerprint: u32,   // Fingerprint of the parent key
+    pub child_number: u32,         // Index of this key
+    pub chain_code: [u8; 32],      // Chain code (32 bytes)
+    pub private_key: SecretKey,    // Secret key (32 bytes)
+    pub origin: Option<KeyOrigin>, // Master fingerprint and path from the master key, if known
+}
+
+impl Xprv {
+    /// Creates a new extended private key with the provided components
+    /// The version defaults to mainnet xprv on the Bitcoin network
+    pub fn new(
+        depth: u8,
+        parent_fingerprint: u32,
+        child_number: u32,
+        chain_code: [u8; 32],
+        private_key: SecretKey,
+    ) -> Self {
+        Self {
+            version: Version::Xpub,
+            network: Network::Bitcoin,
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            private_key,
+            origin: None,
+        }
+    }
+
+    /// Returns a copy of this key with a known origin (master fingerprint and path)
+    /// Children derived from the key, and its public key, extend the origin
+    pub fn with_origin(mut self, origin: KeyOrigin) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Derives the master key from a seed: HMAC-SHA512("Bitcoin seed", seed)
+    pub fn new_master(seed: &[u8]) -> Result<Self, Error> {
+        let mut mac =
+            HmacSha512::new_from_slice(MASTER_KEY_SALT).expect("HMAC can take a key of any size");
+        mac.update(seed);
+        let mut result = mac.finalize().into_bytes();
+
+        // I_L is the master secret key and I_R the master chain code
+        let (i_l, i_r) = result.split_at(32);
+        let private_key = SecretKey::from_slice(i_l).map_err(Error::InvalidPrivateKey);
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(i_r);
+        result.as_mut_slice().zeroize();
+
+        let master = Self::new(0, 0, 0, chain_code, private_key?);
+        chain_code.zeroize();
+        Ok(master)
+    }
+
+    /// Returns a copy of this key serialized under a different version
+    /// The network falls back to the version's default when the network class changes
+    pub fn with_version(mut self, version: Version) -> Self {
+        if version.is_mainnet() != self.network.is_mainnet() {
+            self.network = version.default_network();
+        }
+        self.version = version;
+        self
+    }
+
+    /// Returns a copy of this key bound to the given network
+    /// Only networks matching the version's class are accepted
+    pub fn with_network(mut self, network: Network) -> Result<Self, Error> {
+        if network.is_mainnet() != self.version.is_mainnet() {
+            return Err(Error::NetworkMismatch {
+                network,
+                version: self.version,
+            });
+        }
+        self.network = network;
+        Ok(self)
+    }
+
+    /// Converts a Base58 encoded xprv string into an Xprv instance
+    pub fn from_base58(xprv: &str) -> Result<Self, Error> {
+        // Decode the xprv from Base58
+        let mut decoded = xprv.from_base58()?;
+        let result = Self::from_decoded(&decoded);
+        decoded.zeroize();
+        result
+    }
+
+    /// Parses the decoded bytes of a Base58 encoded xprv
+    fn from_decoded(decoded: &[u8]) -> Result<Self, Error> {
+        if decoded.len() != 82 {
+            return Err(Error::InvalidLength {
+                expected: 82,
+                found: decoded.len(),
+            });
+        }
+
+        // bytes [78..82]: checksum over the 78 byte payload
+        base58check::verify_checksum(decoded)?;
+
+        // Extract components from the decoded xprv
+        // bytes [0..4]: version bytes
+        // bytes [4]: depth
+        // bytes [5..9]: parent fingerprint
+        // bytes [9..13]: child number
+        // bytes [13..45]: chain code
+        // bytes [45]: 0x00 padding
+        // bytes [46..78]: private key
+        let version = Version::from_private_bytes(decoded[0..4].try_into().unwrap())?;
+        let depth = decoded[4];
+        let parent_fingerprint = u32::from_be_bytes(decoded[5..9].try_into().unwrap());
+        let child_number = u32::from_be_bytes(decoded[9..13].try_into().unwrap());
+        let chain_code = decoded[13..45].try_into().unwrap();
+        if decoded[45] != 0x00 {
+            return Err(Error::InvalidPrivateKey(secp256k1::Error::InvalidSecretKey));
+        }
+        let private_key =
+            SecretKey::from_slice(&decoded[46..78]).map_err(Error::InvalidPrivateKey)?;
+        check_master_fields(depth, parent_fingerprint, child_number)?;
+
+        Ok(Self::new(
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            private_key,
+        )
+        .with_version(version))
+    }
+
+    /// Serializes the Xprv into its Base58 string representation
+    pub fn to_base58(&self) -> String {
+        let mut serialized = [0u8; 78];
+
+        // Version bytes (4 bytes)
+        serialized[0..4].copy_from_slice(&self.version.to_private_bytes());
+
+        // Depth (1 byte)
+        serialized[4] = self.depth;
+
+        // Parent fingerprint (4 bytes)
+        serialized[5..9].copy_from_slice(&self.parent_fingerprint.to_be_bytes());
+
+        // Child number (4 bytes)
+        serialized[9..13].copy_from_slice(&self.child_number.to_be_bytes());
+
+        // Chain code (32 bytes)
+        serialized[13..45].copy_from_slice(&self.chain_code);
+
+        // Private key, prefixed with 0x00 (33 bytes)
+        serialized[46..78].copy_from_slice(&self.private_key.secret_bytes());
+
+        // Append checksum and encode
+        let encoded = base58check::encode_check(&serialized);
+        serialized.zeroize();
+        encoded
+    }
+
+    /// Computes the compressed public key of the secret key
+    pub fn public_key(&self) -> PublicKey {
+        self.private_key.public_key(engine::global().context())
+    }
+
+    /// Returns the matching extended public key, with the same chain code and fingerprint
+    pub fn to_xpub(&self) -> Xpub {
+        Xpub {
+            version: self.version,
+            network: self.network,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            public_key: self.public_key(),
+            origin: self.origin.clone(),
+        }
+    }
+
+    /// Calculates the fingerprint (first 4 bytes of HASH160) of the public key
+    pub fn fingerprint(&self) -> u32 {
+        let hash160 = hash::hash160(&self.public_key().serialize());
+
+        u32::from_be_bytes(hash160[0..4].try_into().unwrap())
+    }
+
+    /// Derives a hardened or non-hardened child Xprv from the current Xprv
+    /// Returns `Error::InvalidTweak` for the rare indices BIP32 says to skip
+    pub fn derive_child(&self, index: u32) -> Result<Self, Error> {
+        let depth = self.depth.checked_add(1).ok_or(Error::DepthOverflow)?;
+
+        // Prepare data for HMAC-SHA512
+        // hardened: 0x00 || parent_privkey (32 bytes) || child_index (4 bytes)
+        // non-hardened: parent_pubkey (33 bytes) || child_index (4 bytes)
+        let mut data = [0u8; 37];
+        if path::is_hardened(index) {
+            data[1..33].copy_from_slice(&self.private_key.secret_bytes());
+        } else {
+            data[..33].copy_from_slice(&self.public_key().serialize());
+        }
+        data[33..].copy_from_slice(&index.to_be_bytes());
+
+        // Generate child key material using HMAC-SHA512
+        let mut mac =
+            HmacSha512::new_from_slice(&self.chain_code).expect("HMAC can take a key of any size");
+        mac.update(&data);
+        data.zeroize();
+        let mut result = mac.finalize().into_bytes();
+
+        // Split the result into two 32-byte halves
+        let (i_l, i_r) = result.split_at(32);
+
+        // Child key is parse256(I_L) + parent key (mod n)
+        // I_L >= n or a zero child key make the index invalid
+        let child_key = Scalar::from_be_bytes(i_l.try_into().unwrap())
+            .ok()
+            .and_then(|tweak| self.private_key.add_tweak(&tweak).ok())
+            .ok_or(Error::InvalidTweak(index));
+
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(i_r);
+        result.as_mut_slice().zeroize();
+
+        // Create the child Xprv
+        let fingerprint = self.fingerprint();
+        let child = Self {
+            version: self.version,
+            network: self.network,
+            depth,
+            parent_fingerprint: fingerprint,
+            child_number: index,
+            chain_code,
+            private_key: child_key?,
+            origin: engine::child_origin(self.origin.as_ref(), self.depth, fingerprint, index),
+        };
+        chain_code.zeroize();
+        Ok(child)
+    }
+
+    /// Derives the key at the given path, relative to this Xprv
+    /// Both hardened and non-hardened steps are supported
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Error> {
+        if path.has_wildcard() {
+            return Err(Error::InvalidPath(format!(
+                "cannot derive a single key from wildcard path {}",
+                path
+            )));
+        }
+
+        let mut current = self.clone();
+        for step in path.steps() {
+            current = current.derive_child(*step)?;
+        }
+        Ok(current)
+    }
+
+    /// Derives the account key m/purpose'/coin_type'/account' from this master key
+    /// The coin type follows the key's network and the version follows the purpose
+    pub fn derive_account(&self, purpose: Purpose, account: u32) -> Result<Self, Error> {
+        let path = purpose.account_path(self.network, account)?;
+        Ok(self
+            .derive_path(&path)?
+            .with_version(purpose.version(self.network)))
+    }
+}
+
+impl Drop for Xprv {
+    fn drop(&mut self) {
+        self.private_key.non_secure_erase();
+        self.chain_code.zeroize();
+    }
+}
@@ -0,0 +1,181 @@
+use bip_tools::{Chain, ChainSelection, DerivationPath, Error, IndexRange, ScriptType, Xpub};
+
+#[cfg(test)]
+mod address_iter_tests {
+    use super::*;
+
+    // Same account key as the BIP44 test vectors
+    const TEST_XPUB: &str = "xpub6CQrEh7fCh2jd4kdgqCxAQ4dpzvLGCmx5PM3GLQH1bQRCLWRUMHqeZ5XWi8QUM39BeFeBJaUA5VS4Vvw5oLaA6tHZBifTetFCxj6keSvfFS";
+
+    // Known receive addresses at 0/0, 0/1 and 0/2
+    const EXPECTED_BIP44_ADDRESSES: [&str; 3] = [
+        "1AkcymbeHtiufKa1EgC1TY4E36ehdKVEDt",
+        "1BNedVV6nTX9oN77tMtoToFQ6FGQf8A3sY",
+        "176FPbVE5GScCh7jvMcj6TjBwrecs8BeAR",
+    ];
+
+    // BIP32 Iterator Tests
+
+    #[test]
+    fn test_address_iter_yields_children() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let receive = xpub.derive_non_hardened(0).unwrap();
+        let range = IndexRange::new(0, 3).unwrap();
+
+        let derived: Vec<_> = receive
+            .address_iter(range, ScriptType::P2pkh)
+            .unwrap()
+            .collect();
+        assert_eq!(derived.len(), 3, "One item per index expected");
+
+        for (expected_index, (i, child, address)) in derived.iter().enumerate() {
+            assert_eq!(*i, expected_index as u32, "Index mismatch");
+            assert_eq!(
+                address, EXPECTED_BIP44_ADDRESSES[expected_index],
+                "Address mismatch at index {}",
+                i
+            );
+            assert_eq!(child.child_number, *i, "Child number mismatch");
+            assert_eq!(child.depth, receive.depth + 1, "Child depth mismatch");
+            assert_eq!(
+                child.to_base58(),
+                receive.derive_non_hardened(*i).unwrap().to_base58(),
+                "Child key mismatch at index {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_iter_matches_range() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::new(1000, 1050).unwrap().with_step(7).unwrap();
+
+        for script_type in ScriptType::ALL {
+            let lazy: Vec<(u32, String)> = xpub
+                .address_iter(range, script_type)
+                .unwrap()
+                .map(|(i, _, address)| (i, address))
+                .collect();
+            assert_eq!(
+                lazy,
+                xpub.derive_bip32_address_range(range, script_type).unwrap(),
+                "Iterator and range output differ for {}",
+                script_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_address_iter_is_lazy() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        // Every non-hardened index; only the requested items are derived
+        let range = IndexRange::new(0, 1 << 31).unwrap();
+
+        let mut addresses = xpub.address_iter(range, ScriptType::P2pkh).unwrap();
+        assert_eq!(
+            addresses.size_hint(),
+            (0, Some(1 << 31)),
+            "Upper bound should be the range length"
+        );
+
+        let first: Vec<u32> = addresses.by_ref().take(3).map(|(i, _, _)| i).collect();
+        assert_eq!(first, [0, 1, 2], "First indices mismatch");
+        assert_eq!(
+            addresses.next().map(|(i, _, _)| i),
+            Some(3),
+            "Iteration should resume after the taken items"
+        );
+    }
+
+    #[test]
+    fn test_path_address_iter() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let path: DerivationPath = "0/*".parse().unwrap();
+        let range = IndexRange::new(0, 3).unwrap();
+
+        let addresses: Vec<String> = xpub
+            .path_address_iter(&path, range, ScriptType::P2pkh)
+            .unwrap()
+            .map(|(_, _, address)| address)
+            .collect();
+        assert_eq!(
+            addresses, EXPECTED_BIP44_ADDRESSES,
+            "Path addresses mismatch"
+        );
+    }
+
+    #[test]
+    fn test_address_iter_depth_overflow() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let deepest = Xpub::new(255, 0, 0, xpub.chain_code, xpub.public_key);
+        let range = IndexRange::new(0, 3).unwrap();
+
+        assert!(
+            matches!(
+                deepest.address_iter(range, ScriptType::P2pkh),
+                Err(Error::DepthOverflow)
+            ),
+            "Children of a depth 255 key cannot be derived"
+        );
+    }
+
+    // BIP44 Iterator Tests
+
+    #[test]
+    fn test_bip44_address_iter_order() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::new(0, 3).unwrap();
+
+        let derived: Vec<(Chain, u32)> = xpub
+            .bip44_address_iter(ChainSelection::Both, range, ScriptType::P2pkh)
+            .unwrap()
+            .map(|(chain, i, _, _)| (chain, i))
+            .collect();
+        let expected: Vec<(Chain, u32)> = [Chain::External, Chain::Internal]
+            .iter()
+            .flat_map(|chain| (0..3).map(move |i| (*chain, i)))
+            .collect();
+        assert_eq!(derived, expected, "Receive addresses should come first");
+    }
+
+    #[test]
+    fn test_bip44_address_iter_matches_range() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::new(10, 20).unwrap();
+
+        for selection in [
+            ChainSelection::External,
+            ChainSelection::Internal,
+            ChainSelection::Both,
+        ] {
+            let addresses = xpub
+                .bip44_address_iter(selection, range, ScriptType::P2wpkh)
+                .unwrap();
+            assert_eq!(
+                addresses.size_hint().1,
+                Some(range.len() * selection.chains().len()),
+                "Upper bound mismatch for {:?}",
+                selection
+            );
+
+            let lazy: Vec<(Chain, u32, String)> = addresses
+                .map(|(chain, i, child, address)| {
+                    assert_eq!(
+                        child.to_address(ScriptType::P2wpkh),
+                        address,
+                        "Child key does not match its address"
+                    );
+                    (chain, i, address)
+                })
+                .collect();
+            assert_eq!(
+                lazy,
+                xpub.derive_bip44_address_range(selection, range, ScriptType::P2wpkh)
+                    .unwrap(),
+                "Iterator and range output differ for {:?}",
+                selection
+            );
+        }
+    }
+}
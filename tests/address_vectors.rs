@@ -0,0 +1,286 @@
+use bip_tools::address::Payload;
+use bip_tools::{base58check, Address, AddressType, Error, Network, ScriptType, Xpub};
+
+#[cfg(test)]
+mod address_tests {
+    use super::*;
+
+    // BIP350 valid segwit addresses of every witness version and their scriptPubKey
+    const VALID_SEGWIT_ADDRESSES: [(&str, &str); 8] = [
+        (
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+        ),
+        (
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+        ),
+        (
+            "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+            "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+        ),
+        ("BC1SW50QGDZ25J", "6002751e"),
+        (
+            "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+            "5210751e76e8199196d454941c45d1b3a323",
+        ),
+        (
+            "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+            "0020000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+        ),
+        (
+            "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+            "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+        ),
+        (
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ),
+    ];
+
+    // BIP173 and BIP350 invalid segwit addresses
+    const INVALID_SEGWIT_ADDRESSES: [&str; 16] = [
+        "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+        "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+        "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+        "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+        "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+        "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+        "bc1pw5dgrnzv",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+        "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+        "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+        "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+        "bc1zw508d6qejxtdg4y5r3zarvaryvqyzf3du",
+        "bc1gmk9yu",
+    ];
+
+    // Address of the genesis block coinbase output and its public key hash
+    const GENESIS_ADDRESS: &str = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+    const GENESIS_PUBKEY_HASH: &str = "62e907b15cbf27d5425399ebf6f0fb50ebb88f18";
+
+    // BIP84 account key m/84'/0'/0' of the "abandon ... about" mnemonic
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Segwit Address Tests
+
+    #[test]
+    fn test_bip350_valid_addresses() {
+        for (encoded, script_pubkey) in VALID_SEGWIT_ADDRESSES {
+            let address: Address = encoded
+                .parse()
+                .unwrap_or_else(|e| panic!("{} should parse: {}", encoded, e));
+            assert_eq!(
+                hex(&address.script_pubkey()),
+                script_pubkey,
+                "{} scriptPubKey mismatch",
+                encoded
+            );
+            assert_eq!(
+                address.to_string(),
+                encoded.to_lowercase(),
+                "Re-encoding yields the lowercase form"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_segwit_addresses() {
+        for encoded in INVALID_SEGWIT_ADDRESSES {
+            assert!(
+                Address::parse(encoded).is_err(),
+                "{} should be rejected",
+                encoded
+            );
+        }
+    }
+
+    #[test]
+    fn test_segwit_mixed_case_rejected() {
+        let mixed = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3T4";
+        assert_eq!(Address::parse(mixed).unwrap_err(), Error::Bech32MixedCase);
+    }
+
+    #[test]
+    fn test_segwit_address_types() {
+        let cases = [
+            (VALID_SEGWIT_ADDRESSES[0].0, AddressType::P2wpkh, 0),
+            (VALID_SEGWIT_ADDRESSES[1].0, AddressType::P2wsh, 0),
+            (VALID_SEGWIT_ADDRESSES[2].0, AddressType::WitnessUnknown, 1),
+            (VALID_SEGWIT_ADDRESSES[3].0, AddressType::WitnessUnknown, 16),
+            (VALID_SEGWIT_ADDRESSES[6].0, AddressType::P2tr, 1),
+        ];
+        for (encoded, address_type, version) in cases {
+            let address = Address::parse(encoded).unwrap();
+            assert_eq!(address.address_type(), address_type, "{}", encoded);
+            assert_eq!(address.witness_version(), Some(version), "{}", encoded);
+        }
+    }
+
+    #[test]
+    fn test_built_segwit_addresses() {
+        let program = vec![0x75; 20];
+        let address = Address::new(
+            Network::Bitcoin,
+            Payload::WitnessProgram {
+                version: 0,
+                program: program.clone(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            Address::parse(&address.to_string()).unwrap(),
+            address,
+            "Built segwit address should round trip"
+        );
+
+        let invalid = [
+            (17, program.clone(), Error::InvalidWitnessVersion(17)),
+            (
+                0,
+                vec![0x75; 21],
+                Error::InvalidWitnessProgramLength {
+                    version: 0,
+                    length: 21,
+                },
+            ),
+            (
+                1,
+                vec![0x75; 41],
+                Error::InvalidWitnessProgramLength {
+                    version: 1,
+                    length: 41,
+                },
+            ),
+        ];
+        for (version, program, error) in invalid {
+            let payload = Payload::WitnessProgram { version, program };
+            assert_eq!(
+                Address::new(Network::Bitcoin, payload).unwrap_err(),
+                error,
+                "Invalid witness programs should be rejected when building an address"
+            );
+        }
+    }
+
+    // Base58 Address Tests
+
+    #[test]
+    fn test_base58_addresses() {
+        let address = Address::parse(GENESIS_ADDRESS).unwrap();
+        assert_eq!(address.network(), Network::Bitcoin);
+        assert_eq!(address.address_type(), AddressType::P2pkh);
+        assert_eq!(address.witness_version(), None);
+        assert_eq!(
+            hex(&address.script_pubkey()),
+            format!("76a914{}88ac", GENESIS_PUBKEY_HASH),
+            "P2PKH scriptPubKey mismatch"
+        );
+        assert_eq!(address.to_string(), GENESIS_ADDRESS);
+
+        let hash = match *address.payload() {
+            Payload::PubkeyHash(hash) => hash,
+            _ => panic!("Expected a public key hash"),
+        };
+        let testnet = Address::new(Network::Testnet, Payload::ScriptHash(hash)).unwrap();
+        let parsed = Address::parse(&testnet.to_string()).unwrap();
+        assert_eq!(parsed, testnet, "Test P2SH address should round trip");
+        assert_eq!(
+            hex(&parsed.script_pubkey()),
+            format!("a914{}87", GENESIS_PUBKEY_HASH),
+            "P2SH scriptPubKey mismatch"
+        );
+    }
+
+    #[test]
+    fn test_base58_invalid_addresses() {
+        let mut corrupted = GENESIS_ADDRESS.to_string();
+        corrupted.replace_range(33.., "b");
+        assert!(matches!(
+            Address::parse(&corrupted),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+
+        let short = base58check::encode_check(&[0x00; 20]);
+        assert_eq!(
+            Address::parse(&short).unwrap_err(),
+            Error::InvalidLength {
+                expected: 21,
+                found: 20
+            }
+        );
+
+        let unknown_prefix = base58check::encode_check(&[0x30; 21]);
+        assert_eq!(
+            Address::parse(&unknown_prefix).unwrap_err(),
+            Error::UnknownAddressVersion(0x30),
+            "Unknown version bytes should be rejected"
+        );
+    }
+
+    // Network Tests
+
+    #[test]
+    fn test_address_networks() {
+        let regtest = Address::parse("bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080").unwrap();
+        assert_eq!(regtest.network(), Network::Regtest);
+        assert_eq!(regtest.networks(), [Network::Regtest]);
+
+        let testnet = Address::parse(VALID_SEGWIT_ADDRESSES[1].0).unwrap();
+        assert_eq!(
+            testnet.networks(),
+            [Network::Testnet, Network::Testnet4, Network::Signet],
+            "tb addresses are shared by the test networks except regtest"
+        );
+        let signet = Address::parse_for_network(VALID_SEGWIT_ADDRESSES[1].0, Network::Signet);
+        assert_eq!(signet.unwrap().network(), Network::Signet);
+
+        assert_eq!(
+            Address::parse_for_network(GENESIS_ADDRESS, Network::Testnet).unwrap_err(),
+            Error::AddressNetworkMismatch {
+                address: Network::Bitcoin,
+                network: Network::Testnet
+            }
+        );
+        assert!(
+            Address::parse_for_network(VALID_SEGWIT_ADDRESSES[1].0, Network::Regtest).is_err(),
+            "Regtest uses the bcrt human readable part"
+        );
+    }
+
+    // Generated Address Tests
+
+    #[test]
+    fn test_generated_addresses_round_trip() {
+        let child = Xpub::from_base58(BIP84_ZPUB)
+            .unwrap()
+            .derive_path(&"0/0".parse().unwrap())
+            .unwrap();
+        let expected_types = [
+            AddressType::P2pkh,
+            AddressType::P2sh,
+            AddressType::P2wpkh,
+            AddressType::P2tr,
+        ];
+
+        for (script_type, address_type) in ScriptType::ALL.into_iter().zip(expected_types) {
+            let encoded = child.to_address(script_type);
+            let address = Address::parse(&encoded).unwrap();
+            assert_eq!(address.address_type(), address_type, "{}", script_type);
+            assert_eq!(
+                address.script_pubkey(),
+                child.script_pubkey(script_type),
+                "{} scriptPubKey should match the key's",
+                script_type
+            );
+            assert_eq!(address.to_string(), encoded);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use bip_tools::{bech32, Error, ScriptType, Xpub};
+
+#[cfg(test)]
+mod bip173_tests {
+    use super::*;
+
+    // BIP84 account 0 extended public key (m/84'/0'/0') for the mnemonic
+    // "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    // BIP84 receive addresses m/84'/0'/0'/0/0 and m/84'/0'/0'/0/1
+    const EXPECTED_BIP84_ADDRESSES: [&str; 2] = [
+        "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+        "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+    ];
+
+    // BIP173 strings with a valid bech32 checksum
+    const VALID_CHECKSUMS: [&str; 7] = [
+        "A12UEL5L",
+        "a12uel5l",
+        "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+        "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+        "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+        "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+        "?1ezyfcl",
+    ];
+
+    // BIP173 strings that must fail to decode
+    const INVALID_CHECKSUMS: [&str; 11] = [
+        "\u{20}1nwldj5",
+        "\u{7F}1axkwrx",
+        "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+        "pzry9x0s0muk",
+        "1pzry9x0s0muk",
+        "x1b4n0q5v",
+        "li1dgmt3",
+        "de1lg7wt\u{FF}",
+        "A1G7SGD8",
+        "10a06t8",
+        "1qzzfhee",
+    ];
+
+    // BIP173 witness v0 addresses and their scriptPubKey
+    const VALID_ADDRESSES: [(&str, &str, &str); 3] = [
+        (
+            "bc",
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+            "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+        ),
+        (
+            "tb",
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+        ),
+        (
+            "tb",
+            "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+            "0020000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+        ),
+    ];
+
+    // BIP173 invalid segwit addresses
+    const INVALID_ADDRESSES: [&str; 10] = [
+        "tc1qw508d6qejxtdg4y5r3zarvary0c5xw7kg3g4ty",
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+        "BC13W508D6QEJXTDG4Y5R3ZARVARY0C5XW7KN40WF2",
+        "bc1rw5uspcuh",
+        "bc10w508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kw5rljs90",
+        "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7",
+        "bc1zw508d6qejxtdg4y5r3zarvaryvqyzf3du",
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3pjxtptv",
+        "bc1gmk9yu",
+    ];
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Bech32 Encoding Tests
+
+    #[test]
+    fn test_bip173_valid_checksums() {
+        for valid in VALID_CHECKSUMS {
+            let (hrp, data, variant) =
+                bech32::decode(valid).unwrap_or_else(|e| panic!("{} should decode: {}", valid, e));
+            assert_eq!(variant, bech32::Variant::Bech32, "Should be bech32");
+
+            // Re-encoding yields the lowercase form
+            let encoded = bech32::encode(&hrp, &data, variant).unwrap();
+            assert_eq!(encoded, valid.to_lowercase(), "Round-trip should match");
+        }
+    }
+
+    #[test]
+    fn test_bip173_invalid_checksums() {
+        for invalid in INVALID_CHECKSUMS {
+            assert!(
+                bech32::decode(invalid).is_err(),
+                "{:?} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_bip173_valid_addresses() {
+        for (hrp, address, script_pubkey) in VALID_ADDRESSES {
+            let (version, program) = bech32::decode_segwit_address(hrp, address).unwrap();
+            assert_eq!(version, 0, "Should be witness v0");
+
+            let script = [vec![0x00, program.len() as u8], program.clone()].concat();
+            assert_eq!(to_hex(&script), script_pubkey, "scriptPubKey mismatch");
+
+            let encoded = bech32::encode_segwit_address(hrp, version, &program).unwrap();
+            assert_eq!(encoded, address.to_lowercase(), "Round-trip should match");
+        }
+    }
+
+    #[test]
+    fn test_bip173_invalid_addresses() {
+        for address in INVALID_ADDRESSES {
+            for hrp in ["bc", "tb"] {
+                assert!(
+                    bech32::decode_segwit_address(hrp, address).is_err(),
+                    "{} should be rejected for hrp {}",
+                    address,
+                    hrp
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bip173_invalid_address_errors() {
+        let cases = [
+            (
+                "tb",
+                "tc1qw508d6qejxtdg4y5r3zarvary0c5xw7kg3g4ty",
+                Error::WrongHrp {
+                    expected: "tb".to_string(),
+                    found: "tc".to_string(),
+                },
+            ),
+            (
+                "bc",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+                Error::Bech32Checksum,
+            ),
+            (
+                "bc",
+                "BC13W508D6QEJXTDG4Y5R3ZARVARY0C5XW7KN40WF2",
+                Error::InvalidWitnessVersion(17),
+            ),
+            (
+                "bc",
+                "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+                Error::InvalidWitnessProgramLength {
+                    version: 0,
+                    length: 16,
+                },
+            ),
+            (
+                "bc",
+                "bc1zw508d6qejxtdg4y5r3zarvaryvqyzf3du",
+                Error::WitnessVariantMismatch {
+                    version: 2,
+                    expected: bech32::Variant::Bech32m,
+                    found: bech32::Variant::Bech32,
+                },
+            ),
+            (
+                "tb",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7",
+                Error::Bech32MixedCase,
+            ),
+            ("bc", "bc1gmk9yu", Error::Bech32InvalidData),
+            ("bc", "bcw508d6qejxtdg4y5r3", Error::Bech32MissingSeparator),
+        ];
+        for (hrp, address, expected) in cases {
+            assert_eq!(
+                bech32::decode_segwit_address(hrp, address).unwrap_err(),
+                expected,
+                "Unexpected error for {}",
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn test_bip173_convert_bits_round_trip() {
+        let data = [0x00, 0x14, 0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91];
+        let five_bit = bech32::convert_bits(&data, 8, 5, true).unwrap();
+        assert!(five_bit.iter().all(|v| *v < 32), "Values should be 5 bits");
+        assert_eq!(bech32::convert_bits(&five_bit, 5, 8, false).unwrap(), data);
+    }
+
+    // P2WPKH Address Tests
+
+    #[test]
+    fn test_bip173_p2wpkh_known_addresses() {
+        let xpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        let addresses = xpub
+            .derive_bip44_addresses_with(2, ScriptType::P2wpkh)
+            .unwrap();
+
+        assert_eq!(
+            addresses, EXPECTED_BIP84_ADDRESSES,
+            "Derived addresses should match BIP84 test vectors"
+        );
+    }
+
+    #[test]
+    fn test_bip173_p2wpkh_matches_p2pkh_hash() {
+        // Both address types commit to the same HASH160
+        let xpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        let child = xpub.derive_non_hardened(0).unwrap();
+
+        let (version, program) =
+            bech32::decode_segwit_address("bc", &child.to_p2wpkh_address()).unwrap();
+        assert_eq!(version, 0);
+        assert_eq!(program, child.pubkey_hash(), "Program should be HASH160");
+    }
+
+    #[test]
+    fn test_bip173_to_address_dispatch() {
+        let xpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        assert_eq!(
+            xpub.to_address(ScriptType::P2pkh),
+            xpub.to_bitcoin_address()
+        );
+        assert_eq!(
+            xpub.to_address(ScriptType::P2wpkh),
+            xpub.to_p2wpkh_address()
+        );
+        assert_eq!("segwit".parse::<ScriptType>().unwrap(), ScriptType::P2wpkh);
+        assert!("p2xyz".parse::<ScriptType>().is_err());
+    }
+}
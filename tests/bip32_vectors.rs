@@ -1,9 +1,9 @@
-use bip_tools::Xpub;
+use bip_tools::{base58check, Error, Version, Xpub};
 
 #[cfg(test)]
 mod bip32_tests {
-    use crate::Xpub;
-    use base58::FromBase58;
+    use crate::{base58check, Error, Version, Xpub};
+    use base58::{FromBase58, ToBase58};
     use secp256k1::PublicKey;
     use sha2::{Digest, Sha256};
 
@@ -26,6 +26,20 @@ mod bip32_tests {
         PublicKey::from_slice(&decoded[45..78]).unwrap()
     }
 
+    // Helper function to replace the checksum of decoded xpub bytes with a valid one
+    fn with_valid_checksum(mut data: Vec<u8>) -> String {
+        let checksum = Sha256::digest(Sha256::digest(&data[..78]));
+        data[78..82].copy_from_slice(&checksum[..4]);
+        data.to_base58()
+    }
+
+    // Helper function to re-encode the test key under other version bytes
+    fn encode_with_version(version: [u8; 4]) -> String {
+        let mut data = TEST_XPUB.from_base58().unwrap();
+        data[0..4].copy_from_slice(&version);
+        with_valid_checksum(data)
+    }
+
     // Basic Structure Tests
 
     #[test]
@@ -71,30 +85,33 @@ mod bip32_tests {
         // Test invalid Base58 characters
         let result = Xpub::from_base58("invalid!base58@string");
         assert!(result.is_err());
-        assert!(result
-            .as_ref()
-            .err()
-            .unwrap()
-            .contains("Base58 decode error"));
+        assert!(
+            matches!(result, Err(Error::Base58(_))),
+            "Should be a Base58 decode error"
+        );
 
         // Test invalid lenght
         let result = Xpub::from_base58("1aaaaaaaa");
         assert!(result.is_err());
-        assert!(result
-            .as_ref()
-            .err()
-            .unwrap()
-            .contains("Invalid xpub length"));
+        assert!(
+            matches!(
+                result,
+                Err(Error::InvalidLength {
+                    expected: 82,
+                    found: 7
+                })
+            ),
+            "Should be an invalid length error"
+        );
 
-        // Test invalid public key
+        // Test invalid public key (with a valid checksum, so the key itself is checked)
         let invalid_xpub = "xpub6CUGRUonZSQ4zHWHPYWmGLs3ySaVP7envEXHHYQFDvD85JQBY6kw5VexFge6qcCYwQFhbgFLRqCzq3JHcthYMSLf1r3kzjqFiGN1ZNDSqLv";
-        let result = Xpub::from_base58(&invalid_xpub);
+        let result = Xpub::from_base58(&with_valid_checksum(invalid_xpub.from_base58().unwrap()));
         assert!(result.is_err());
-        assert!(result
-            .as_ref()
-            .err()
-            .unwrap()
-            .contains("Invalid public key"));
+        assert!(
+            matches!(result, Err(Error::InvalidPublicKey(_))),
+            "Should be an invalid public key error"
+        );
     }
 
     // Format and Structural Checks
@@ -113,10 +130,120 @@ mod bip32_tests {
         let main_data = &decoded[0..decoded.len() - 4];
         let provided_checksum = &decoded[decoded.len() - 4..];
 
-        let calculated_checksum = &Sha256::digest(&Sha256::digest(main_data))[..4];
+        let calculated_checksum = &Sha256::digest(Sha256::digest(main_data))[..4];
         assert_eq!(calculated_checksum, provided_checksum, "Invalid checksum");
     }
 
+    #[test]
+    fn test_bip32_xpub_checksum_flipped_characters() {
+        // A single mistyped character must never decode into a different key
+        const ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+        for position in 4..TEST_XPUB.len() {
+            let original = TEST_XPUB.as_bytes()[position] as char;
+            let replacement = ALPHABET.chars().find(|c| *c != original).unwrap();
+
+            let mut mistyped = TEST_XPUB.to_string();
+            mistyped.replace_range(position..position + 1, &replacement.to_string());
+
+            let result = Xpub::from_base58(&mistyped);
+            let err = result
+                .err()
+                .unwrap_or_else(|| panic!("Flipped character at {} should be rejected", position));
+            assert!(
+                matches!(err, Error::ChecksumMismatch { .. }),
+                "Position {}: unexpected error: {}",
+                position,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_bip32_xpub_corrupted_checksum() {
+        // Valid payload with a wrong checksum
+        let mut data = TEST_XPUB.from_base58().unwrap();
+        data[81] ^= 0x01;
+
+        let result = Xpub::from_base58(&data.to_base58());
+        assert!(result.is_err(), "Corrupted checksum should be rejected");
+        assert!(matches!(result, Err(Error::ChecksumMismatch { .. })));
+    }
+
+    #[test]
+    fn test_bip32_base58check_round_trip() {
+        // The same Base58Check layer is used for addresses
+        let payload = [0x00; 21];
+        let encoded = base58check::encode_check(&payload);
+        assert_eq!(encoded, "1111111111111111111114oLvT2");
+        assert_eq!(base58check::decode_check(&encoded).unwrap(), payload);
+
+        let result = base58check::decode_check("1111111111111111111114oLvT3");
+        assert!(matches!(result, Err(Error::ChecksumMismatch { .. })));
+    }
+
+    #[test]
+    fn test_bip32_version_detection() {
+        // Every public SLIP-132 version should be detected and round-trip unchanged
+        for version in Version::ALL {
+            let encoded = encode_with_version(version.to_bytes());
+            assert!(
+                encoded.starts_with(version.prefix()),
+                "Encoded key should start with {}",
+                version
+            );
+
+            let xpub = Xpub::from_base58(&encoded).unwrap();
+            assert_eq!(xpub.version, version, "Version should be detected");
+            assert_eq!(xpub.to_base58(), encoded, "{} should round-trip", version);
+        }
+    }
+
+    #[test]
+    fn test_bip32_tpub_round_trip() {
+        // A tpub must not be rewritten as an xpub
+        let tpub = encode_with_version([0x04, 0x35, 0x87, 0xCF]);
+        let xpub = Xpub::from_base58(&tpub).unwrap();
+
+        assert_eq!(xpub.version, Version::Tpub, "Should parse as tpub");
+        assert!(!xpub.version.is_mainnet(), "tpub is not a mainnet version");
+        assert_eq!(xpub.to_base58(), tpub, "tpub should round-trip exactly");
+
+        // Children keep the version of their parent
+        let child = xpub.derive_non_hardened(0).unwrap();
+        assert!(
+            child.to_base58().starts_with("tpub"),
+            "Child should be tpub"
+        );
+    }
+
+    #[test]
+    fn test_bip32_private_version_rejected() {
+        // Extended private key versions must not be parsed as public keys
+        for (bytes, prefix) in [
+            ([0x04, 0x88, 0xAD, 0xE4], "xprv"),
+            ([0x04, 0x35, 0x83, 0x94], "tprv"),
+            ([0x04, 0xB2, 0x43, 0x0C], "zprv"),
+        ] {
+            let result = Xpub::from_base58(&encode_with_version(bytes));
+            let err = result.err().expect("Private version should be rejected");
+            assert_eq!(err, Error::PrivateKeyVersion(prefix));
+            assert!(
+                err.to_string().contains(prefix),
+                "Error should name {}",
+                prefix
+            );
+        }
+    }
+
+    #[test]
+    fn test_bip32_unknown_version_rejected() {
+        let result = Xpub::from_base58(&encode_with_version([0xDE, 0xAD, 0xBE, 0xEF]));
+        let err = result.err().expect("Unknown version should be rejected");
+        assert_eq!(err, Error::UnknownVersion([0xDE, 0xAD, 0xBE, 0xEF]));
+        assert_eq!(err.to_string(), "Unknown version bytes: 0xDEADBEEF");
+    }
+
     #[test]
     fn test_bip32_to_base58() {
         let decoded_test = TEST_XPUB.from_base58().unwrap();
@@ -220,6 +347,11 @@ mod bip32_tests {
             xpub.derive_non_hardened(0x80000000).is_err(),
             "Should fail with hardened index"
         );
+        assert_eq!(
+            xpub.derive_non_hardened(0x80000000).err(),
+            Some(Error::HardenedDerivation(0x80000000)),
+            "Hardened index should not be reported as an invalid tweak"
+        );
 
         // Test maximum allowed index
         assert!(
@@ -228,6 +360,36 @@ mod bip32_tests {
         );
     }
 
+    #[test]
+    fn test_bip32_depth_overflow() {
+        // Depth is a single byte, so a key at depth 255 has no serializable children
+        let mut xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        xpub.depth = 255;
+        assert_eq!(
+            xpub.derive_non_hardened(0).err(),
+            Some(Error::DepthOverflow),
+            "Deriving below depth 255 should overflow"
+        );
+
+        xpub.depth = 254;
+        assert_eq!(xpub.derive_non_hardened(0).unwrap().depth, 255);
+    }
+
+    #[test]
+    fn test_bip32_error_source() {
+        use std::error::Error as _;
+
+        // Invalid public keys keep the underlying secp256k1 error as their source
+        let invalid_xpub = "xpub6CUGRUonZSQ4zHWHPYWmGLs3ySaVP7envEXHHYQFDvD85JQBY6kw5VexFge6qcCYwQFhbgFLRqCzq3JHcthYMSLf1r3kzjqFiGN1ZNDSqLv";
+        let err = Xpub::from_base58(&with_valid_checksum(invalid_xpub.from_base58().unwrap()))
+            .err()
+            .unwrap();
+        assert!(err.source().is_some(), "Should expose the secp256k1 error");
+        assert!(err.to_string().starts_with("Invalid public key"));
+
+        assert!(Error::DepthOverflow.source().is_none());
+    }
+
     // Advanced Derivation Tests
 
     #[test]
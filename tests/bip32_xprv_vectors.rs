@@ -0,0 +1,356 @@
+use bip_tools::path::is_hardened;
+use bip_tools::{DerivationPath, Error, Network, Version, Xprv, Xpub};
+
+#[cfg(test)]
+mod bip32_xprv_tests {
+    use super::*;
+
+    // A derivation path with its expected extended private and public keys
+    type Case = (&'static str, &'static str, &'static str);
+
+    // BIP32 test vector 1
+    const SEED_1: &str = "000102030405060708090a0b0c0d0e0f";
+    const VECTOR_1: [Case; 6] = [
+        (
+            "m",
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+        ),
+        (
+            "m/0h",
+            "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+            "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+        ),
+        (
+            "m/0h/1",
+            "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+            "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+        ),
+        (
+            "m/0h/1/2h",
+            "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+            "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+        ),
+        (
+            "m/0h/1/2h/2",
+            "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+            "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+        ),
+        (
+            "m/0h/1/2h/2/1000000000",
+            "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+            "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+        ),
+    ];
+
+    // BIP32 test vector 2
+    const SEED_2: &str = "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+    const VECTOR_2: [Case; 6] = [
+        (
+            "m",
+            "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+            "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB",
+        ),
+        (
+            "m/0",
+            "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+            "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH",
+        ),
+        (
+            "m/0/2147483647h",
+            "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+            "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a",
+        ),
+        (
+            "m/0/2147483647h/1",
+            "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+            "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon",
+        ),
+        (
+            "m/0/2147483647h/1/2147483646h",
+            "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+            "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL",
+        ),
+        (
+            "m/0/2147483647h/1/2147483646h/2",
+            "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+            "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt",
+        ),
+    ];
+
+    // BIP32 test vector 3: retention of leading zeros in the private key
+    const SEED_3: &str = "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be";
+    const VECTOR_3: [Case; 2] = [
+        (
+            "m",
+            "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+            "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+        ),
+        (
+            "m/0h",
+            "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+            "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+        ),
+    ];
+
+    // BIP32 test vector 4: retention of leading zeros in hardened derivation
+    const SEED_4: &str = "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678";
+    const VECTOR_4: [Case; 3] = [
+        (
+            "m",
+            "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+            "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa",
+        ),
+        (
+            "m/0h",
+            "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+            "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m",
+        ),
+        (
+            "m/0h/1h",
+            "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+            "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt",
+        ),
+    ];
+
+    // BIP32 test vector 5: invalid extended keys and the reason they are rejected
+    fn vector_5() -> Vec<(&'static str, &'static str, Error)> {
+        vec![
+            (
+                "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm",
+                "pubkey version / prvkey mismatch",
+                Error::InvalidPublicKey(secp256k1::Error::InvalidPublicKey),
+            ),
+            (
+                "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH",
+                "prvkey version / pubkey mismatch",
+                Error::InvalidPrivateKey(secp256k1::Error::InvalidSecretKey),
+            ),
+            (
+                "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn",
+                "invalid pubkey prefix 04",
+                Error::InvalidPublicKey(secp256k1::Error::InvalidPublicKey),
+            ),
+            (
+                "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ",
+                "invalid prvkey prefix 04",
+                Error::InvalidPrivateKey(secp256k1::Error::InvalidSecretKey),
+            ),
+            (
+                "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4",
+                "invalid pubkey prefix 01",
+                Error::InvalidPublicKey(secp256k1::Error::InvalidPublicKey),
+            ),
+            (
+                "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J",
+                "invalid prvkey prefix 01",
+                Error::InvalidPrivateKey(secp256k1::Error::InvalidSecretKey),
+            ),
+            (
+                "xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv",
+                "zero depth with non-zero parent fingerprint",
+                Error::InvalidMasterKey,
+            ),
+            (
+                "xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ",
+                "zero depth with non-zero parent fingerprint",
+                Error::InvalidMasterKey,
+            ),
+            (
+                "xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN",
+                "zero depth with non-zero index",
+                Error::InvalidMasterKey,
+            ),
+            (
+                "xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8",
+                "zero depth with non-zero index",
+                Error::InvalidMasterKey,
+            ),
+            (
+                "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4",
+                "unknown extended key version",
+                Error::UnknownVersion([0; 4]),
+            ),
+            (
+                "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHPmHJiEDXkTiJTVV9rHEBUem2mwVbbNfvT2MTcAqj3nesx8uBf9",
+                "unknown extended key version",
+                Error::UnknownVersion([0; 4]),
+            ),
+            (
+                "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx",
+                "private key 0 not in 1..n-1",
+                Error::InvalidPrivateKey(secp256k1::Error::InvalidSecretKey),
+            ),
+            (
+                "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD5SDKr24z3aiUvKr9bJpdrcLg1y3G",
+                "private key n not in 1..n-1",
+                Error::InvalidPrivateKey(secp256k1::Error::InvalidSecretKey),
+            ),
+            (
+                "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY",
+                "invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007",
+                Error::InvalidPublicKey(secp256k1::Error::InvalidPublicKey),
+            ),
+            (
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL",
+                "invalid checksum",
+                Error::ChecksumMismatch {
+                    expected: [0; 4],
+                    found: [0; 4],
+                },
+            ),
+        ]
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // Checks every case of a vector, deriving from the master key and step by step
+    fn check_vector(seed: &str, cases: &[Case]) {
+        let master = Xprv::new_master(&from_hex(seed)).unwrap();
+
+        for (path, expected_xprv, expected_xpub) in cases {
+            let path: DerivationPath = path.parse().unwrap();
+            let xprv = master.derive_path(&path).unwrap();
+            assert_eq!(xprv.to_base58(), *expected_xprv, "xprv at {}", path);
+            assert_eq!(
+                xprv.to_xpub().to_base58(),
+                *expected_xpub,
+                "xpub at {}",
+                path
+            );
+
+            // Both serializations should round-trip
+            assert_eq!(
+                Xprv::from_base58(expected_xprv).unwrap().to_base58(),
+                *expected_xprv
+            );
+            assert_eq!(
+                Xpub::from_base58(expected_xpub).unwrap().to_base58(),
+                *expected_xpub
+            );
+
+            // Public derivation must agree for the non-hardened last step
+            if let Some((&last, parent_steps)) = path.steps().split_last() {
+                let parent = master
+                    .derive_path(&DerivationPath::new(parent_steps.to_vec()))
+                    .unwrap()
+                    .to_xpub();
+                match parent.derive_non_hardened(last) {
+                    Ok(child) => assert_eq!(child.to_base58(), *expected_xpub),
+                    Err(e) => {
+                        assert!(is_hardened(last), "Unexpected error at {}: {}", path, e);
+                        assert_eq!(e, Error::HardenedDerivation(last));
+                    }
+                }
+            }
+        }
+    }
+
+    // Derivation Vectors
+
+    #[test]
+    fn test_bip32_vector_1() {
+        check_vector(SEED_1, &VECTOR_1);
+    }
+
+    #[test]
+    fn test_bip32_vector_2() {
+        check_vector(SEED_2, &VECTOR_2);
+    }
+
+    #[test]
+    fn test_bip32_vector_3() {
+        check_vector(SEED_3, &VECTOR_3);
+    }
+
+    #[test]
+    fn test_bip32_vector_4() {
+        check_vector(SEED_4, &VECTOR_4);
+    }
+
+    #[test]
+    fn test_bip32_vector_5() {
+        for (key, reason, expected) in vector_5() {
+            let err = if key.starts_with("xpub") {
+                Xpub::from_base58(key).err()
+            } else {
+                Xprv::from_base58(key).err()
+            }
+            .unwrap_or_else(|| panic!("Key should be rejected: {}", reason));
+
+            assert_eq!(
+                std::mem::discriminant(&err),
+                std::mem::discriminant(&expected),
+                "{}: unexpected error {}",
+                reason,
+                err
+            );
+        }
+    }
+
+    // Conversion and Version Tests
+
+    #[test]
+    fn test_xprv_to_xpub_matches_fields() {
+        let xprv = Xprv::from_base58(VECTOR_1[2].1).unwrap();
+        let xpub = xprv.to_xpub();
+
+        assert_eq!(xpub.chain_code, xprv.chain_code, "Chain code should match");
+        assert_eq!(
+            xpub.fingerprint(),
+            xprv.fingerprint(),
+            "Fingerprint should match"
+        );
+        assert_eq!(xpub.parent_fingerprint, xprv.parent_fingerprint);
+        assert_eq!(xpub.depth, 2);
+        assert_eq!(xpub.child_number, 1);
+        assert_eq!(xpub.public_key, xprv.public_key());
+    }
+
+    #[test]
+    fn test_xprv_rejects_public_versions() {
+        let err = Xprv::from_base58(VECTOR_1[0].2).err().unwrap();
+        assert_eq!(err, Error::PublicKeyVersion("xpub"));
+
+        let err = Xpub::from_base58(VECTOR_1[0].1).err().unwrap();
+        assert_eq!(err, Error::PrivateKeyVersion("xprv"));
+    }
+
+    #[test]
+    fn test_xprv_version_round_trip() {
+        let master = Xprv::new_master(&from_hex(SEED_1))
+            .unwrap()
+            .with_version(Version::Vpub);
+        assert_eq!(master.network, Network::Testnet);
+
+        let encoded = master.to_base58();
+        assert!(encoded.starts_with("vprv"), "Should encode as vprv");
+
+        let parsed = Xprv::from_base58(&encoded).unwrap();
+        assert_eq!(parsed.version, Version::Vpub);
+        assert!(parsed.to_xpub().to_base58().starts_with("vpub"));
+        assert!(parsed.clone().with_network(Network::Bitcoin).is_err());
+        assert!(parsed.with_network(Network::Regtest).is_ok());
+    }
+
+    #[test]
+    fn test_xprv_hardened_child_numbers() {
+        let master = Xprv::new_master(&from_hex(SEED_1)).unwrap();
+        let child = master.derive_child(0x8000_0000).unwrap();
+
+        assert_eq!(child.child_number, 0x8000_0000);
+        assert_eq!(child.parent_fingerprint, master.fingerprint());
+        assert_eq!(child.to_base58(), VECTOR_1[1].1);
+
+        let wildcard: DerivationPath = "m/0/*".parse().unwrap();
+        assert!(matches!(
+            master.derive_path(&wildcard),
+            Err(Error::InvalidPath(_))
+        ));
+    }
+}
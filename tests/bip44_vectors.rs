@@ -1,5 +1,5 @@
 use base58::FromBase58;
-use bip_tools::Xpub;
+use bip_tools::{Chain, ChainSelection, ScriptType, Xpub};
 use sha2::{Digest, Sha256};
 
 #[cfg(test)]
@@ -33,7 +33,7 @@ mod bip44_test {
         ];
 
         for (invalid_xpub, error_msg) in &test_cases {
-            assert!(Xpub::from_base58(*invalid_xpub).is_err(), "{}", error_msg);
+            assert!(Xpub::from_base58(invalid_xpub).is_err(), "{}", error_msg);
         }
     }
 
@@ -84,7 +84,7 @@ mod bip44_test {
         let main_data = &decoded[0..&decoded.len() - 4];
         let provided_checksum = &decoded[&decoded.len() - 4..];
 
-        let calculated_checksum = &Sha256::digest(&Sha256::digest(main_data))[..4];
+        let calculated_checksum = &Sha256::digest(Sha256::digest(main_data))[..4];
         assert_eq!(
             calculated_checksum, provided_checksum,
             "Checksum should match"
@@ -265,4 +265,78 @@ mod bip44_test {
             "Derived addresses should match expected values"
         );
     }
+
+    // Change Chain Tests
+
+    #[test]
+    fn test_bip44_internal_chain_addresses() {
+        // Change addresses follow account/1/i
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let change = xpub
+            .derive_bip44_chain_addresses(Chain::Internal, 2, ScriptType::P2pkh)
+            .unwrap();
+
+        let internal = xpub.derive_non_hardened(1).unwrap();
+        for (i, address) in change.iter().enumerate() {
+            assert_eq!(
+                address,
+                &internal
+                    .derive_non_hardened(i as u32)
+                    .unwrap()
+                    .to_bitcoin_address(),
+                "Change address {} should be derived from account/1/{}",
+                i,
+                i
+            );
+        }
+
+        // The external chain still matches the known receive addresses
+        let receive = xpub
+            .derive_bip44_chain_addresses(Chain::External, 3, ScriptType::P2pkh)
+            .unwrap();
+        assert_eq!(receive, EXPECTED_BIP44_ADDRESSES);
+    }
+
+    #[test]
+    fn test_bip44_both_chains_labeled() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let both = xpub
+            .derive_bip44_selected_addresses(ChainSelection::Both, 3, ScriptType::P2pkh)
+            .unwrap();
+
+        assert_eq!(both.len(), 6, "Should derive 3 addresses per chain");
+        for (i, expected) in EXPECTED_BIP44_ADDRESSES.iter().enumerate() {
+            assert_eq!(both[i], (Chain::External, i as u32, expected.to_string()));
+        }
+
+        let change = xpub
+            .derive_bip44_chain_addresses(Chain::Internal, 3, ScriptType::P2pkh)
+            .unwrap();
+        for (i, expected) in change.iter().enumerate() {
+            assert_eq!(both[3 + i], (Chain::Internal, i as u32, expected.clone()));
+        }
+    }
+
+    #[test]
+    fn test_bip44_chain_selection_parse() {
+        assert_eq!(
+            "external".parse::<ChainSelection>().unwrap(),
+            ChainSelection::External
+        );
+        assert_eq!(
+            "change".parse::<ChainSelection>().unwrap(),
+            ChainSelection::Internal
+        );
+        assert_eq!(
+            "both".parse::<ChainSelection>().unwrap(),
+            ChainSelection::Both
+        );
+        assert!("sideways".parse::<ChainSelection>().is_err());
+
+        assert_eq!(
+            ChainSelection::Both.chains(),
+            [Chain::External, Chain::Internal]
+        );
+        assert_eq!(Chain::Internal.index(), 1);
+    }
 }
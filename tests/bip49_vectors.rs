@@ -0,0 +1,114 @@
+use base58::FromBase58;
+use bip_tools::{Error, ScriptType, Version, Xpub};
+
+#[cfg(test)]
+mod bip49_tests {
+    use super::*;
+
+    // BIP49 testnet account 0 extended public key (m/49'/1'/0') for the mnemonic
+    // "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+    const BIP49_UPUB: &str = "upub5EFU65HtV5TeiSHmZZm7FUffBGy8UKeqp7vw43jYbvZPpoVsgU93oac7Wk3u6moKegAEWtGNF8DehrnHtv21XXEMYRUocHqguyjknFHYfgY";
+
+    // BIP49 testnet receive address m/49'/1'/0'/0/0
+    const EXPECTED_TESTNET_ADDRESS: &str = "2Mww8dCYPUpKHofjgcXcBCEGmniw9CoaiD2";
+
+    // Mainnet account 0 extended public key (m/49'/0'/0') for the same mnemonic
+    const BIP49_YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+
+    // Mainnet receive address m/49'/0'/0'/0/0
+    const EXPECTED_MAINNET_ADDRESS: &str = "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf";
+
+    #[test]
+    fn test_bip49_version_detection() {
+        let upub = Xpub::from_base58(BIP49_UPUB).unwrap();
+        assert_eq!(upub.version, Version::Upub, "Should detect upub");
+        assert_eq!(upub.version.script_type(), ScriptType::P2shP2wpkh);
+
+        let ypub = Xpub::from_base58(BIP49_YPUB).unwrap();
+        assert_eq!(ypub.version, Version::Ypub, "Should detect ypub");
+        assert_eq!(ypub.version.script_type(), ScriptType::P2shP2wpkh);
+    }
+
+    #[test]
+    fn test_bip49_testnet_address() {
+        // A upub automatically produces testnet P2SH-P2WPKH addresses
+        let upub = Xpub::from_base58(BIP49_UPUB).unwrap();
+        let addresses = upub.derive_bip44_addresses(1).unwrap();
+
+        assert_eq!(addresses[0], EXPECTED_TESTNET_ADDRESS);
+        assert!(addresses[0].starts_with('2'), "Testnet P2SH starts with 2");
+    }
+
+    #[test]
+    fn test_bip49_mainnet_address() {
+        // A ypub automatically produces mainnet P2SH-P2WPKH addresses
+        let ypub = Xpub::from_base58(BIP49_YPUB).unwrap();
+        let addresses = ypub.derive_bip44_addresses(1).unwrap();
+
+        assert_eq!(addresses[0], EXPECTED_MAINNET_ADDRESS);
+        assert!(addresses[0].starts_with('3'), "Mainnet P2SH starts with 3");
+    }
+
+    #[test]
+    fn test_bip49_p2sh_prefix() {
+        let ypub = Xpub::from_base58(BIP49_YPUB).unwrap();
+        let decoded = ypub.to_p2sh_p2wpkh_address().from_base58().unwrap();
+        assert_eq!(decoded[0], 0x05, "Should use P2SH version byte");
+
+        let upub = Xpub::from_base58(BIP49_UPUB).unwrap();
+        let decoded = upub.to_p2sh_p2wpkh_address().from_base58().unwrap();
+        assert_eq!(decoded[0], 0xC4, "Should use testnet P2SH version byte");
+    }
+
+    #[test]
+    fn test_bip49_explicit_script_type() {
+        // The implied script type can be overridden explicitly
+        let ypub = Xpub::from_base58(BIP49_YPUB).unwrap();
+        let nested = ypub
+            .derive_bip44_addresses_with(1, ScriptType::P2shP2wpkh)
+            .unwrap();
+        let legacy = ypub
+            .derive_bip44_addresses_with(1, ScriptType::P2pkh)
+            .unwrap();
+
+        assert_eq!(nested[0], EXPECTED_MAINNET_ADDRESS);
+        assert!(legacy[0].starts_with('1'), "Should be P2PKH when requested");
+    }
+
+    #[test]
+    fn test_bip49_implied_script_types() {
+        assert_eq!(Version::Xpub.script_type(), ScriptType::P2pkh);
+        assert_eq!(Version::Tpub.script_type(), ScriptType::P2pkh);
+        assert_eq!(Version::Zpub.script_type(), ScriptType::P2wpkh);
+        assert_eq!(Version::Vpub.script_type(), ScriptType::P2wpkh);
+        assert_eq!(Version::YpubMultisig.script_type(), ScriptType::P2shP2wpkh);
+    }
+
+    #[test]
+    fn test_multisig_version_has_no_single_key_type() {
+        assert_eq!(
+            Version::Ypub.single_key_script_type(),
+            Ok(ScriptType::P2shP2wpkh)
+        );
+        assert_eq!(
+            Version::ZpubMultisig.single_key_script_type(),
+            Err(Error::MultisigVersion(Version::ZpubMultisig))
+        );
+
+        // The same key material under the multisig Ypub version
+        let ypub = Xpub::from_base58(BIP49_YPUB).unwrap();
+        let multisig = ypub.clone().with_version(Version::YpubMultisig);
+        assert_eq!(
+            multisig.derive_bip44_addresses(1).unwrap_err(),
+            Error::MultisigVersion(Version::YpubMultisig),
+            "Multisig keys should not imply single key addresses"
+        );
+        assert_eq!(
+            multisig
+                .derive_bip44_addresses_with(1, ScriptType::P2shP2wpkh)
+                .unwrap(),
+            ypub.derive_bip44_addresses(1).unwrap(),
+            "An explicit script type should still be accepted"
+        );
+    }
+}
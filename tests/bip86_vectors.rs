@@ -0,0 +1,198 @@
+use bip_tools::bech32::{self, Variant};
+use bip_tools::{ScriptType, Xpub};
+
+#[cfg(test)]
+mod bip86_tests {
+    use super::*;
+
+    // BIP86 account 0 extended public key (m/86'/0'/0') for the mnemonic
+    // "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+    const BIP86_XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+
+    // BIP86 receive addresses m/86'/0'/0'/0/0 and m/86'/0'/0'/0/1
+    const EXPECTED_RECEIVE_ADDRESSES: [&str; 2] = [
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+        "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+    ];
+
+    // BIP86 change address m/86'/0'/0'/1/0
+    const EXPECTED_CHANGE_ADDRESS: &str =
+        "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7";
+
+    // Internal and output keys of m/86'/0'/0'/0/0
+    const INTERNAL_KEY: &str = "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115";
+    const OUTPUT_KEY: &str = "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c";
+
+    // BIP350 strings with a valid bech32m checksum
+    const VALID_BECH32M: [&str; 7] = [
+        "A1LQFN3A",
+        "a1lqfn3a",
+        "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6",
+        "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+        "11llllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllludsr8",
+        "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+        "?1v759aa",
+    ];
+
+    // BIP350 valid segwit addresses of every witness version
+    const VALID_ADDRESSES: [(&str, &str); 8] = [
+        ("bc", "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"),
+        (
+            "tb",
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+        ),
+        (
+            "bc",
+            "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+        ),
+        ("bc", "BC1SW50QGDZ25J"),
+        ("bc", "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs"),
+        (
+            "tb",
+            "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+        ),
+        (
+            "tb",
+            "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+        ),
+        (
+            "bc",
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+        ),
+    ];
+
+    // BIP350 invalid segwit addresses
+    const INVALID_ADDRESSES: [&str; 15] = [
+        "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+        "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+        "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+        "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+        "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+        "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+        "bc1pw5dgrnzv",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+        "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+        "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+        "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+        "bc1gmk9yu",
+    ];
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Bech32m Encoding Tests
+
+    #[test]
+    fn test_bip350_valid_checksums() {
+        for valid in VALID_BECH32M {
+            let (hrp, data, variant) =
+                bech32::decode(valid).unwrap_or_else(|e| panic!("{} should decode: {}", valid, e));
+            assert_eq!(variant, Variant::Bech32m, "{} should be bech32m", valid);
+
+            let encoded = bech32::encode(&hrp, &data, variant).unwrap();
+            assert_eq!(encoded, valid.to_lowercase(), "Round-trip should match");
+        }
+
+        // The bech32m checksum computed over the uppercase hrp is invalid
+        assert!(bech32::decode("M1VUXWEZ").is_err());
+    }
+
+    #[test]
+    fn test_bip350_valid_addresses() {
+        for (hrp, address) in VALID_ADDRESSES {
+            let (version, program) = bech32::decode_segwit_address(hrp, address)
+                .unwrap_or_else(|e| panic!("{} should decode: {}", address, e));
+
+            let encoded = bech32::encode_segwit_address(hrp, version, &program).unwrap();
+            assert_eq!(encoded, address.to_lowercase(), "Round-trip should match");
+        }
+    }
+
+    #[test]
+    fn test_bip350_invalid_addresses() {
+        for address in INVALID_ADDRESSES {
+            for hrp in ["bc", "tb"] {
+                assert!(
+                    bech32::decode_segwit_address(hrp, address).is_err(),
+                    "{} should be rejected for hrp {}",
+                    address,
+                    hrp
+                );
+            }
+        }
+    }
+
+    // BIP86 Taproot Tests
+
+    #[test]
+    fn test_bip86_output_key() {
+        let xpub = Xpub::from_base58(BIP86_XPUB).unwrap();
+        let child = xpub
+            .derive_non_hardened(0)
+            .unwrap()
+            .derive_non_hardened(0)
+            .unwrap();
+
+        let (internal_key, _) = child.public_key.x_only_public_key();
+        assert_eq!(to_hex(&internal_key.serialize()), INTERNAL_KEY);
+        assert_eq!(to_hex(&child.taproot_output_key().serialize()), OUTPUT_KEY);
+    }
+
+    #[test]
+    fn test_bip86_receive_addresses() {
+        let xpub = Xpub::from_base58(BIP86_XPUB).unwrap();
+        let addresses = xpub
+            .derive_bip44_addresses_with(2, ScriptType::P2tr)
+            .unwrap();
+
+        assert_eq!(
+            addresses, EXPECTED_RECEIVE_ADDRESSES,
+            "Derived addresses should match BIP86 test vectors"
+        );
+    }
+
+    #[test]
+    fn test_bip86_change_address() {
+        let xpub = Xpub::from_base58(BIP86_XPUB).unwrap();
+        let change = xpub
+            .derive_non_hardened(1)
+            .unwrap()
+            .derive_non_hardened(0)
+            .unwrap();
+
+        assert_eq!(change.to_p2tr_address(), EXPECTED_CHANGE_ADDRESS);
+        assert_eq!(change.to_address(ScriptType::P2tr), EXPECTED_CHANGE_ADDRESS);
+    }
+
+    #[test]
+    fn test_bip86_address_is_witness_v1() {
+        let xpub = Xpub::from_base58(BIP86_XPUB).unwrap();
+        let address = xpub.to_p2tr_address();
+
+        let (version, program) = bech32::decode_segwit_address("bc", &address).unwrap();
+        assert_eq!(version, 1, "P2TR uses witness version 1");
+        assert_eq!(program, xpub.taproot_output_key().serialize());
+    }
+
+    #[test]
+    fn test_parse_p2tr_script_type() {
+        for name in ["p2tr", "P2TR", "taproot", "bech32m"] {
+            assert_eq!(
+                name.parse::<ScriptType>().unwrap(),
+                ScriptType::P2tr,
+                "{} should parse as P2TR",
+                name
+            );
+        }
+        for script_type in ScriptType::ALL {
+            assert_eq!(
+                script_type.name().parse::<ScriptType>().unwrap(),
+                script_type
+            );
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use bip_tools::{ConversionWarning, Error, Network, ScriptType, Version, Xpub};
+
+#[cfg(test)]
+mod convert_tests {
+    use super::*;
+
+    // BIP84 account key m/84'/0'/0' of the "abandon ... about" mnemonic and the same
+    // key with xpub version bytes, as used in Bitcoin Core descriptors
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+    const BIP84_XPUB: &str = "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+
+    // BIP49 account key m/49'/0'/0'
+    const BIP49_YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+
+    // Version Prefix Tests
+
+    #[test]
+    fn test_version_prefix_parsing() {
+        for version in Version::ALL {
+            assert_eq!(
+                version.prefix().parse::<Version>().unwrap(),
+                version,
+                "Prefix should round trip"
+            );
+        }
+        assert_eq!("Zpub".parse::<Version>().unwrap(), Version::ZpubMultisig);
+        assert_eq!(
+            "ZPUB".parse::<Version>().unwrap_err(),
+            Error::UnknownVersionPrefix("ZPUB".to_string()),
+            "Prefixes are case sensitive"
+        );
+        assert!("zprv".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn test_version_script_type_names() {
+        assert_eq!(Version::Xpub.script_type_name(), "p2pkh");
+        assert_eq!(Version::Upub.script_type_name(), "p2sh-p2wpkh");
+        assert_eq!(Version::YpubMultisig.script_type_name(), "p2sh-p2wsh");
+        assert_eq!(Version::VpubMultisig.script_type_name(), "p2wsh");
+    }
+
+    // Conversion Tests
+
+    #[test]
+    fn test_convert_zpub_to_xpub() {
+        let zpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        let conversion = zpub.convert(Version::Xpub);
+        assert_eq!(conversion.to_base58(), BIP84_XPUB, "Converted key mismatch");
+        assert_eq!(conversion.from, Version::Zpub);
+        assert_eq!(conversion.to(), Version::Xpub);
+
+        let back = Xpub::from_base58(BIP84_XPUB)
+            .unwrap()
+            .convert(Version::Zpub);
+        assert_eq!(back.to_base58(), BIP84_ZPUB, "Conversion should round trip");
+    }
+
+    #[test]
+    fn test_convert_keeps_key_material() {
+        let ypub = Xpub::from_base58(BIP49_YPUB).unwrap();
+        for version in Version::ALL {
+            let converted = Xpub::from_base58(&ypub.convert(version).to_base58()).unwrap();
+            assert_eq!(converted.version, version);
+            assert_eq!(converted.depth, ypub.depth);
+            assert_eq!(converted.parent_fingerprint, ypub.parent_fingerprint);
+            assert_eq!(converted.child_number, ypub.child_number);
+            assert_eq!(converted.chain_code, ypub.chain_code);
+            assert_eq!(
+                converted.public_key, ypub.public_key,
+                "{} conversion changed the public key",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_addresses() {
+        let zpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        let xpub = zpub.convert(Version::Xpub).xpub;
+        let child = |xpub: &Xpub| {
+            xpub.derive_non_hardened(0)
+                .unwrap()
+                .derive_non_hardened(0)
+                .unwrap()
+        };
+
+        assert_eq!(
+            child(&xpub).to_address(ScriptType::P2wpkh),
+            child(&zpub).to_address(ScriptType::P2wpkh),
+            "The same script type gives the same address"
+        );
+        assert_ne!(
+            child(&xpub).to_address(xpub.version.script_type()),
+            child(&zpub).to_address(zpub.version.script_type()),
+            "The implied script type gives another address"
+        );
+
+        let vpub = zpub.convert(Version::Vpub).xpub;
+        assert_eq!(vpub.network, Network::Testnet);
+        assert!(child(&vpub)
+            .to_address(ScriptType::P2wpkh)
+            .starts_with("tb1q"));
+    }
+
+    // Warning Tests
+
+    #[test]
+    fn test_convert_warnings() {
+        let zpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+
+        assert!(zpub.convert(Version::Zpub).warnings().is_empty());
+        assert_eq!(
+            zpub.convert(Version::Vpub).warnings(),
+            [ConversionWarning::NetworkChanged {
+                from: Network::Bitcoin,
+                to: Network::Testnet
+            }],
+            "Only the network changes from zpub to vpub"
+        );
+
+        let conversion = zpub.convert(Version::Xpub);
+        assert!(conversion.changes_script_type());
+        assert!(!conversion.changes_network());
+        assert_eq!(
+            conversion.warnings(),
+            [ConversionWarning::ScriptTypeChanged {
+                from: Version::Zpub,
+                to: Version::Xpub
+            }]
+        );
+        assert_eq!(
+            conversion.warnings()[0].to_string(),
+            "implied script type changes from p2wpkh (zpub) to p2pkh (xpub); \
+             keep p2wpkh addresses by using the key in a wpkh() descriptor"
+        );
+
+        assert!(
+            zpub.convert(Version::ZpubMultisig).changes_script_type(),
+            "Single key and multisig versions imply different script types"
+        );
+        assert_eq!(zpub.convert(Version::Tpub).warnings().len(), 2);
+
+        let vpub = zpub.convert(Version::Vpub).xpub;
+        let regtest = vpub.with_network(Network::Regtest).unwrap();
+        assert_eq!(
+            regtest.convert(Version::Zpub).warnings(),
+            [ConversionWarning::NetworkChanged {
+                from: Network::Regtest,
+                to: Network::Bitcoin
+            }],
+            "The warning should name the network the key was used on"
+        );
+    }
+}
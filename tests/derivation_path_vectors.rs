@@ -0,0 +1,166 @@
+use bip_tools::path::HARDENED_OFFSET;
+use bip_tools::{DerivationPath, Error, ScriptType, Xpub};
+
+#[cfg(test)]
+mod derivation_path_tests {
+    use super::*;
+
+    // Same account key as the BIP44 test vectors
+    const TEST_XPUB: &str = "xpub6CQrEh7fCh2jd4kdgqCxAQ4dpzvLGCmx5PM3GLQH1bQRCLWRUMHqeZ5XWi8QUM39BeFeBJaUA5VS4Vvw5oLaA6tHZBifTetFCxj6keSvfFS";
+
+    // Known receive addresses at 0/0, 0/1 and 0/2
+    const EXPECTED_RECEIVE_ADDRESSES: [&str; 3] = [
+        "1AkcymbeHtiufKa1EgC1TY4E36ehdKVEDt",
+        "1BNedVV6nTX9oN77tMtoToFQ6FGQf8A3sY",
+        "176FPbVE5GScCh7jvMcj6TjBwrecs8BeAR",
+    ];
+
+    // Path Parsing Tests
+
+    #[test]
+    fn test_path_parse_and_display() {
+        let cases = [
+            ("m/0/5/12", "m/0/5/12"),
+            ("0/5/12", "m/0/5/12"),
+            ("m/44'/0'/0'/0/1", "m/44'/0'/0'/0/1"),
+            ("m/84h/0h/0H", "m/84'/0'/0'"),
+            ("0/*", "m/0/*"),
+            ("m", "m"),
+            ("", "m"),
+        ];
+
+        for (input, expected) in cases {
+            let path: DerivationPath = input.parse().unwrap();
+            assert_eq!(path.to_string(), expected, "Display of {}", input);
+            assert_eq!(
+                expected.parse::<DerivationPath>().unwrap(),
+                path,
+                "Display output should parse back"
+            );
+        }
+
+        // Relative paths are written without the leading "m"
+        let path: DerivationPath = "m/0/5/*".parse().unwrap();
+        assert_eq!(path.to_relative_string(), "0/5/*");
+        assert_eq!(DerivationPath::default().to_relative_string(), "");
+    }
+
+    #[test]
+    fn test_path_steps() {
+        let path: DerivationPath = "m/44'/0h/2147483647/*".parse().unwrap();
+        assert_eq!(
+            path.steps(),
+            [44 + HARDENED_OFFSET, HARDENED_OFFSET, 0x7FFF_FFFF]
+        );
+        assert!(path.has_wildcard(), "Should have a wildcard");
+        assert_eq!(path.first_hardened(), Some((0, 44 + HARDENED_OFFSET)));
+    }
+
+    #[test]
+    fn test_path_invalid() {
+        let invalid = [
+            "m/",
+            "m//0",
+            "x/0",
+            "m/-1",
+            "m/0x10",
+            "m/2147483648",
+            "m/2147483648'",
+            "m/*/0",
+            "m/0/*'",
+            "m/1''",
+            "m/ 1",
+        ];
+
+        for input in invalid {
+            assert!(
+                input.parse::<DerivationPath>().is_err(),
+                "{:?} should be rejected",
+                input
+            );
+        }
+    }
+
+    // Path Derivation Tests
+
+    #[test]
+    fn test_path_derive_matches_manual_derivation() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let path: DerivationPath = "m/0/5/12".parse().unwrap();
+
+        let derived = xpub.derive_path(&path).unwrap();
+        let manual = xpub
+            .derive_non_hardened(0)
+            .unwrap()
+            .derive_non_hardened(5)
+            .unwrap()
+            .derive_non_hardened(12)
+            .unwrap();
+
+        assert_eq!(derived.to_base58(), manual.to_base58());
+        assert_eq!(derived.depth, xpub.depth + 3, "Depth should increase by 3");
+    }
+
+    #[test]
+    fn test_path_derive_empty_is_identity() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let derived = xpub.derive_path(&DerivationPath::default()).unwrap();
+        assert_eq!(derived.to_base58(), TEST_XPUB);
+    }
+
+    #[test]
+    fn test_path_hardened_step_rejected() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let path: DerivationPath = "m/0/44'/1".parse().unwrap();
+
+        let err = xpub.derive_path(&path).err().unwrap();
+        assert_eq!(
+            err,
+            Error::HardenedDerivation(44 + HARDENED_OFFSET),
+            "Error should name the hardened step"
+        );
+        assert!(
+            err.to_string().contains("hardened child 44'"),
+            "Unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_path_wildcard_addresses() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let path: DerivationPath = "0/*".parse().unwrap();
+
+        let addresses = xpub
+            .derive_path_addresses(&path, 3, ScriptType::P2pkh)
+            .unwrap();
+        assert_eq!(addresses, EXPECTED_RECEIVE_ADDRESSES);
+
+        // The wildcard is implied when omitted
+        let implied = xpub
+            .derive_path_addresses(&"0".parse().unwrap(), 3, ScriptType::P2pkh)
+            .unwrap();
+        assert_eq!(implied, addresses);
+
+        // A wildcard path does not name a single key
+        assert!(xpub.derive_path(&path).is_err());
+    }
+
+    #[test]
+    fn test_path_change_chain() {
+        // Change chain addresses are reachable through the path
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let change = xpub
+            .derive_path_addresses(&"1/*".parse().unwrap(), 1, ScriptType::P2pkh)
+            .unwrap();
+        let manual = xpub
+            .derive_non_hardened(1)
+            .unwrap()
+            .derive_non_hardened(0)
+            .unwrap()
+            .to_bitcoin_address();
+
+        assert_eq!(change[0], manual);
+        assert_ne!(change[0], EXPECTED_RECEIVE_ADDRESSES[0]);
+    }
+}
@@ -0,0 +1,378 @@
+use bip_tools::descriptor::checksum;
+use bip_tools::{DerivationPath, Descriptor, Error, IndexRange, KeyOrigin, Network, ScriptType};
+
+#[cfg(test)]
+mod descriptor_tests {
+    use super::*;
+
+    // Account keys of the "abandon ... about" mnemonic (BIP44, BIP84 and BIP86 vectors)
+    const BIP44_XPUB: &str = "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj";
+    const BIP49_YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+    const BIP84_XPUB: &str = "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+    const BIP86_XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+
+    fn parse(descriptor: &str) -> Descriptor {
+        descriptor.parse().unwrap()
+    }
+
+    // BIP380 Checksum Tests
+
+    #[test]
+    fn test_descriptor_checksum() {
+        assert_eq!(
+            checksum("raw(deadbeef)").unwrap(),
+            "89f8spxm",
+            "BIP380 checksum mismatch"
+        );
+        assert!(
+            matches!(
+                checksum("raw(deadbeef)\u{e9}"),
+                Err(Error::InvalidDescriptor(_))
+            ),
+            "Characters outside the descriptor charset should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_descriptor_round_trip() {
+        let descriptors = [
+            "wpkh([2cbe2a6d/44'/0'/0']tpubDCvNhURocXGZsLNqWcqD3syHTqPXrMSTwi8feKVwAcpi29oYKsDD3Vex7x2TDneKMVN23RbLprfxB69v94iYqdaYHsVz3kPR37NQXeqouVz/0/*)#nhdxg96s",
+            "wpkh([a12b02f4/44'/0'/0']xpub6BzhLAQUDcBUfHRQHZxDF2AbcJqp4Kaeq6bzJpXrjrWuK26ymTFwkEFbxPra2bJ7yeZKbDjfDeFwxe93JMqpo5SsPJH6dZdvV9kMzJkAZ69/0/*)#u37l7u8u",
+            "sh(wpkh(xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL))#tjp2zm88",
+        ];
+        for descriptor in descriptors {
+            assert_eq!(
+                parse(descriptor).to_string(),
+                descriptor,
+                "Descriptor should round-trip"
+            );
+        }
+    }
+
+    #[test]
+    fn test_descriptor_without_checksum() {
+        let descriptor = parse("wpkh([a12b02f4/44h/0h/0h]xpub6BzhLAQUDcBUfHRQHZxDF2AbcJqp4Kaeq6bzJpXrjrWuK26ymTFwkEFbxPra2bJ7yeZKbDjfDeFwxe93JMqpo5SsPJH6dZdvV9kMzJkAZ69/0/*)");
+        assert_eq!(descriptor.checksum(), "u37l7u8u", "Checksum mismatch");
+        assert!(
+            descriptor.to_string().ends_with("'/0'/0']xpub6BzhLAQUDcBUfHRQHZxDF2AbcJqp4Kaeq6bzJpXrjrWuK26ymTFwkEFbxPra2bJ7yeZKbDjfDeFwxe93JMqpo5SsPJH6dZdvV9kMzJkAZ69/0/*)#u37l7u8u"),
+            "Hardened steps should be displayed with '"
+        );
+    }
+
+    #[test]
+    fn test_descriptor_checksum_mismatch() {
+        let result = "raw(deadbeef)#89f8spxx".parse::<Descriptor>();
+        assert_eq!(
+            result.err(),
+            Some(Error::DescriptorChecksumMismatch {
+                expected: "89f8spxm".to_string(),
+                found: "89f8spxx".to_string(),
+            }),
+            "Checksum mismatch should be rejected before parsing"
+        );
+    }
+
+    // Key Expression Tests
+
+    #[test]
+    fn test_descriptor_key_origin() {
+        let descriptor = parse(&format!("wpkh([73c5da0a/84h/0h/0h]{}/0/*)", BIP84_XPUB));
+        let key = descriptor.key();
+        assert_eq!(
+            key.origin,
+            Some(KeyOrigin {
+                fingerprint: 0x73c5da0a,
+                path: "m/84'/0'/0'".parse().unwrap(),
+            }),
+            "Key origin mismatch"
+        );
+        assert_eq!(
+            key.path,
+            DerivationPath::new(vec![0]).with_wildcard(),
+            "Key path mismatch"
+        );
+        assert!(descriptor.is_ranged(), "Descriptor should be ranged");
+        assert_eq!(descriptor.script_type(), ScriptType::P2wpkh);
+    }
+
+    #[test]
+    fn test_descriptor_invalid_keys() {
+        let hardened = format!("wpkh({}/0h/*)", BIP84_XPUB);
+        assert_eq!(
+            hardened.parse::<Descriptor>().err(),
+            Some(Error::HardenedDerivation(0x8000_0000)),
+            "Hardened steps below an xpub should be rejected"
+        );
+
+        let single = "wpkh(020000000000000000000000000000000000000000000000000000000000000002)";
+        assert!(
+            matches!(single.parse::<Descriptor>(), Err(Error::InvalidDescriptor(msg)) if msg.contains("extended")),
+            "Single keys should be rejected"
+        );
+
+        let fingerprint = format!("wpkh([73c5da/84h]{}/0/*)", BIP84_XPUB);
+        assert!(
+            matches!(fingerprint.parse::<Descriptor>(), Err(Error::InvalidDescriptor(msg)) if msg.contains("fingerprint")),
+            "Short fingerprints should be rejected"
+        );
+
+        let unterminated = format!("wpkh([73c5da0a/84h{}/0/*)", BIP84_XPUB);
+        assert!(
+            matches!(
+                unterminated.parse::<Descriptor>(),
+                Err(Error::InvalidDescriptor(_))
+            ),
+            "Unterminated key origins should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_descriptor_unsupported() {
+        let cases = [
+            format!("combo({})", BIP44_XPUB),
+            format!("sh(pkh({}))", BIP44_XPUB),
+            format!("tr({},pk({}))", BIP86_XPUB, BIP44_XPUB),
+        ];
+        for descriptor in cases {
+            assert!(
+                matches!(
+                    descriptor.parse::<Descriptor>(),
+                    Err(Error::InvalidDescriptor(_))
+                ),
+                "{} should be rejected",
+                descriptor
+            );
+        }
+    }
+
+    // Address Derivation Tests
+
+    #[test]
+    fn test_descriptor_addresses() {
+        let cases = [
+            (
+                format!("pkh([73c5da0a/44h/0h/0h]{}/0/*)", BIP44_XPUB),
+                [
+                    "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+                    "1Ak8PffB2meyfYnbXZR9EGfLfFZVpzJvQP",
+                ],
+            ),
+            (
+                format!("sh(wpkh([73c5da0a/49h/0h/0h]{}/0/*))", BIP49_YPUB),
+                [
+                    "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+                    "3LtMnn87fqUeHBUG414p9CWwnoV6E2pNKS",
+                ],
+            ),
+            (
+                format!("wpkh([73c5da0a/84h/0h/0h]{}/0/*)", BIP84_XPUB),
+                [
+                    "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+                    "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+                ],
+            ),
+            (
+                format!("tr([73c5da0a/86h/0h/0h]{}/0/*)", BIP86_XPUB),
+                [
+                    "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+                    "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+                ],
+            ),
+        ];
+
+        for (descriptor, expected) in cases {
+            let descriptor = parse(&descriptor);
+            let addresses = descriptor
+                .address_range(IndexRange::new(0, 2).unwrap())
+                .unwrap();
+            assert_eq!(
+                addresses,
+                vec![(0, expected[0].to_string()), (1, expected[1].to_string())],
+                "Address mismatch for {}",
+                descriptor
+            );
+            assert_eq!(
+                descriptor.address(1).unwrap(),
+                expected[1],
+                "Single address mismatch for {}",
+                descriptor
+            );
+        }
+    }
+
+    #[test]
+    fn test_descriptor_change_address() {
+        let descriptor = parse(&format!("wpkh({}/1/*)", BIP84_XPUB));
+        assert_eq!(
+            descriptor.address(0).unwrap(),
+            "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el",
+            "Change address mismatch"
+        );
+    }
+
+    #[test]
+    fn test_descriptor_fixed_key() {
+        let descriptor = parse(&format!("wpkh({}/0/0)", BIP84_XPUB));
+        assert!(!descriptor.is_ranged(), "Descriptor should not be ranged");
+        assert_eq!(
+            descriptor.address(7).unwrap(),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            "The index should be ignored without a wildcard"
+        );
+        assert!(
+            matches!(
+                descriptor.address_range(IndexRange::new(0, 2).unwrap()),
+                Err(Error::InvalidDescriptor(_))
+            ),
+            "A range requires a wildcard"
+        );
+    }
+
+    #[test]
+    fn test_descriptor_network() {
+        let descriptor = parse("wpkh([2cbe2a6d/44'/0'/0']tpubDCvNhURocXGZsLNqWcqD3syHTqPXrMSTwi8feKVwAcpi29oYKsDD3Vex7x2TDneKMVN23RbLprfxB69v94iYqdaYHsVz3kPR37NQXeqouVz/0/*)");
+        assert_eq!(descriptor.network(), Network::Testnet);
+        let testnet = descriptor.address(0).unwrap();
+        assert!(
+            testnet.starts_with("tb1q"),
+            "tpub should default to testnet"
+        );
+
+        let regtest = descriptor.clone().with_network(Network::Regtest).unwrap();
+        assert!(
+            regtest.address(0).unwrap().starts_with("bcrt1q"),
+            "Regtest addresses should use the bcrt HRP"
+        );
+        assert!(
+            matches!(
+                descriptor.with_network(Network::Bitcoin),
+                Err(Error::NetworkMismatch { .. })
+            ),
+            "Mainnet should not be accepted for a tpub"
+        );
+    }
+
+    // BIP389 Multipath Descriptor Tests
+
+    #[test]
+    fn test_multipath_round_trip() {
+        let descriptor = parse(&format!("wpkh([73c5da0a/84h/0h/0h]{}/<0;1>/*)", BIP84_XPUB));
+        assert!(descriptor.is_multipath(), "Descriptor should be multipath");
+        assert_eq!(descriptor.branch_count(), 2, "Branch count mismatch");
+
+        let formatted = descriptor.to_string();
+        assert!(
+            formatted.contains("/<0;1>/*)#"),
+            "Multipath step should be displayed"
+        );
+        assert_eq!(
+            parse(&formatted).to_string(),
+            formatted,
+            "Multipath descriptor should round-trip"
+        );
+    }
+
+    #[test]
+    fn test_multipath_branches() {
+        let descriptor = parse(&format!("wpkh([73c5da0a/84h/0h/0h]{}/<0;1>/*)", BIP84_XPUB));
+        let branches: Vec<String> = descriptor
+            .branches()
+            .iter()
+            .map(|branch| branch.to_string())
+            .collect();
+        let expected: Vec<String> = ["0", "1"]
+            .iter()
+            .map(|chain| {
+                parse(&format!(
+                    "wpkh([73c5da0a/84h/0h/0h]{}/{}/*)",
+                    BIP84_XPUB, chain
+                ))
+                .to_string()
+            })
+            .collect();
+        assert_eq!(
+            branches, expected,
+            "Branches should be single path descriptors"
+        );
+
+        let single = parse(&format!("wpkh({}/0/*)", BIP84_XPUB));
+        assert_eq!(single.branch_count(), 1, "Single path has one branch");
+        assert_eq!(
+            single.branches()[0].to_string(),
+            single.to_string(),
+            "Single path descriptor is its only branch"
+        );
+    }
+
+    #[test]
+    fn test_multipath_addresses() {
+        let descriptor = parse(&format!("wpkh({}/<0;1>/*)", BIP84_XPUB));
+        let addresses = descriptor
+            .branch_address_range(IndexRange::new(0, 2).unwrap())
+            .unwrap();
+        let expected = vec![
+            (
+                0,
+                0,
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu".to_string(),
+            ),
+            (
+                0,
+                1,
+                "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g".to_string(),
+            ),
+            (
+                1,
+                0,
+                "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el".to_string(),
+            ),
+            (
+                1,
+                1,
+                "bc1qggnasd834t54yulsep6fta8lpjekv4zj6gv5rf".to_string(),
+            ),
+        ];
+        assert_eq!(addresses, expected, "Branch addresses mismatch");
+
+        assert!(
+            matches!(descriptor.address(0), Err(Error::InvalidDescriptor(_))),
+            "Multipath descriptors must be expanded before deriving a single address"
+        );
+    }
+
+    #[test]
+    fn test_multipath_invalid() {
+        let cases = [
+            ("duplicate index", format!("wpkh({}/<0;0>/*)", BIP84_XPUB)),
+            ("single index", format!("wpkh({}/<0>/*)", BIP84_XPUB)),
+            (
+                "two multipath steps",
+                format!("wpkh({}/<0;1>/<2;3>/*)", BIP84_XPUB),
+            ),
+            ("unterminated tuple", format!("wpkh({}/<0;1/*)", BIP84_XPUB)),
+        ];
+        for (reason, descriptor) in cases {
+            assert!(
+                matches!(
+                    descriptor.parse::<Descriptor>(),
+                    Err(Error::InvalidDescriptor(_))
+                ),
+                "{} should be rejected",
+                reason
+            );
+        }
+
+        let hardened = format!("wpkh({}/<0;1h>/*)", BIP84_XPUB);
+        assert_eq!(
+            hardened.parse::<Descriptor>().err(),
+            Some(Error::HardenedDerivation(0x8000_0001)),
+            "Hardened multipath indices should be rejected below an xpub"
+        );
+
+        let origin = format!("wpkh([73c5da0a/84h/<0;1>]{}/0/*)", BIP84_XPUB);
+        assert!(
+            matches!(origin.parse::<Descriptor>(), Err(Error::InvalidPath(_))),
+            "Multipath steps are not allowed in key origins"
+        );
+    }
+}
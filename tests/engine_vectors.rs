@@ -0,0 +1,135 @@
+use bip_tools::engine::{self, Engine};
+use bip_tools::{ChainSelection, DerivationPath, Error, IndexRange, ScriptType, Xpub};
+use secp256k1::Secp256k1;
+
+#[cfg(test)]
+mod engine_tests {
+    use super::*;
+
+    // Same account key as the BIP44 test vectors
+    const TEST_XPUB: &str = "xpub6CQrEh7fCh2jd4kdgqCxAQ4dpzvLGCmx5PM3GLQH1bQRCLWRUMHqeZ5XWi8QUM39BeFeBJaUA5VS4Vvw5oLaA6tHZBifTetFCxj6keSvfFS";
+
+    // BIP86 account key of the "abandon ... about" mnemonic and its first receive address
+    const BIP86_XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+    const BIP86_ADDRESS: &str = "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr";
+
+    // Context Tests
+
+    #[test]
+    fn test_engines_derive_same_keys() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let verify_only = Engine::verification_only();
+        let full = Engine::new();
+        let caller_context = Engine::from_context(Secp256k1::verification_only());
+
+        for index in [0, 1, 1000, (1 << 31) - 1] {
+            let expected = xpub.derive_non_hardened(index).unwrap().to_base58();
+            assert_eq!(
+                verify_only
+                    .derive_non_hardened(&xpub, index)
+                    .unwrap()
+                    .to_base58(),
+                expected,
+                "Verification-only engine mismatch at {}",
+                index
+            );
+            assert_eq!(
+                full.derive_non_hardened(&xpub, index).unwrap().to_base58(),
+                expected,
+                "Full engine mismatch at {}",
+                index
+            );
+            assert_eq!(
+                caller_context
+                    .derive_non_hardened(&xpub, index)
+                    .unwrap()
+                    .to_base58(),
+                expected,
+                "Caller context engine mismatch at {}",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_engine_taproot_address() {
+        let xpub = Xpub::from_base58(BIP86_XPUB).unwrap();
+        let engine = Engine::verification_only();
+
+        let receive = engine
+            .derive_path(&xpub, &"0/0".parse::<DerivationPath>().unwrap())
+            .unwrap();
+        assert_eq!(
+            engine.address(&receive, ScriptType::P2tr),
+            BIP86_ADDRESS,
+            "BIP86 address mismatch"
+        );
+        assert_eq!(
+            engine.taproot_output_key(&receive),
+            receive.taproot_output_key(),
+            "Output key mismatch between engines"
+        );
+    }
+
+    #[test]
+    fn test_engine_rejects_hardened() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let engine = Engine::verification_only();
+
+        assert!(
+            matches!(
+                engine.derive_non_hardened(&xpub, 1 << 31),
+                Err(Error::HardenedDerivation(_))
+            ),
+            "Hardened index should be rejected"
+        );
+        assert!(
+            matches!(
+                engine.derive_path(&xpub, &"0/1'".parse::<DerivationPath>().unwrap()),
+                Err(Error::HardenedDerivation(_))
+            ),
+            "Hardened path step should be rejected"
+        );
+    }
+
+    // Bulk Derivation Tests
+
+    #[test]
+    fn test_engine_iterators_match_xpub() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let engine = Engine::verification_only();
+        let range = IndexRange::new(0, 20).unwrap();
+
+        let addresses: Vec<(u32, String)> = engine
+            .address_iter(&xpub, range, ScriptType::P2shP2wpkh)
+            .unwrap()
+            .map(|(i, _, address)| (i, address))
+            .collect();
+        assert_eq!(
+            addresses,
+            xpub.derive_bip32_address_range(range, ScriptType::P2shP2wpkh)
+                .unwrap(),
+            "BIP32 addresses mismatch"
+        );
+
+        let addresses: Vec<_> = engine
+            .bip44_address_iter(&xpub, ChainSelection::Both, range, ScriptType::P2tr)
+            .unwrap()
+            .map(|(chain, i, _, address)| (chain, i, address))
+            .collect();
+        assert_eq!(
+            addresses,
+            xpub.derive_bip44_address_range(ChainSelection::Both, range, ScriptType::P2tr)
+                .unwrap(),
+            "BIP44 addresses mismatch"
+        );
+    }
+
+    #[test]
+    fn test_global_engine_is_shared() {
+        assert!(
+            std::ptr::eq(engine::global(), engine::global()),
+            "The global engine should be created once"
+        );
+    }
+}
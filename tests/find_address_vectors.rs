@@ -0,0 +1,223 @@
+use bip_tools::{AddressType, Chain, ChainSelection, Error, IndexRange, Network, ScriptType, Xpub};
+
+#[cfg(test)]
+mod find_address_tests {
+    use super::*;
+
+    // Account keys of the "abandon ... about" mnemonic (BIP44, BIP49, BIP84 and BIP86 vectors)
+    const BIP44_XPUB: &str = "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj";
+    const BIP49_YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+    const BIP86_XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+
+    fn range(count: u32) -> IndexRange {
+        IndexRange::from_count(0, count).unwrap()
+    }
+
+    // Script Type Detection Tests
+
+    #[test]
+    fn test_script_type_from_address() {
+        let cases = [
+            ("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", ScriptType::P2pkh),
+            ("37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf", ScriptType::P2shP2wpkh),
+            (
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+                ScriptType::P2wpkh,
+            ),
+            (
+                "BC1QCR8TE4KR609GCAWUTMRZA0J4XV80JY8Z306FYU",
+                ScriptType::P2wpkh,
+            ),
+            (
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+                ScriptType::P2tr,
+            ),
+        ];
+        for (address, expected) in cases {
+            assert_eq!(
+                ScriptType::from_address(address, Network::Bitcoin).unwrap(),
+                expected,
+                "Script type mismatch for {}",
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn test_script_type_from_invalid_address() {
+        let wrong_network = [
+            ("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", Network::Testnet),
+            (
+                "tb1qjzm2wpjl0z0gtuuu24aaqx5dejekhvmqh2l2k8",
+                Network::Bitcoin,
+            ),
+            (
+                "tb1qjzm2wpjl0z0gtuuu24aaqx5dejekhvmqh2l2k8",
+                Network::Regtest,
+            ),
+        ];
+        for (address, network) in wrong_network {
+            assert!(
+                matches!(
+                    ScriptType::from_address(address, network),
+                    Err(Error::AddressNetworkMismatch { .. })
+                ),
+                "{} should be rejected on {}",
+                address,
+                network
+            );
+        }
+
+        // P2WSH is not a single key output
+        let p2wsh = "bc1qpq2cfgz5lktxzr5zqv7nrzz46hsvq3492ump9pz8rzcl8wqtwqcspx5y6a";
+        assert_eq!(
+            ScriptType::from_address(p2wsh, Network::Bitcoin).unwrap_err(),
+            Error::UnsupportedAddressType(AddressType::P2wsh),
+            "P2WSH addresses should be rejected"
+        );
+
+        assert!(
+            matches!(
+                ScriptType::from_address("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabB", Network::Bitcoin),
+                Err(Error::ChecksumMismatch { .. })
+            ),
+            "Corrupted addresses should be rejected"
+        );
+        assert_eq!(
+            ScriptType::from_address(
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyv",
+                Network::Bitcoin
+            )
+            .unwrap_err(),
+            Error::Bech32Checksum,
+            "A bech32 address with a bad checksum should report the checksum"
+        );
+    }
+
+    // Address Lookup Tests
+
+    #[test]
+    fn test_find_address_script_types() {
+        let cases = [
+            (
+                BIP44_XPUB,
+                "1Ak8PffB2meyfYnbXZR9EGfLfFZVpzJvQP",
+                ScriptType::P2pkh,
+            ),
+            (
+                BIP49_YPUB,
+                "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+                ScriptType::P2shP2wpkh,
+            ),
+            (
+                BIP84_ZPUB,
+                "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g",
+                ScriptType::P2wpkh,
+            ),
+            (
+                BIP86_XPUB,
+                "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+                ScriptType::P2tr,
+            ),
+        ];
+        for (xpub, address, script_type) in cases {
+            let xpub = Xpub::from_base58(xpub).unwrap();
+            let found = xpub
+                .find_address(address, ChainSelection::Both, range(20))
+                .unwrap()
+                .expect("address should be found");
+            assert_eq!(found.script_type, script_type, "Script type mismatch");
+            assert_eq!(found.chain, Chain::External, "Chain mismatch");
+            let expected_index = if script_type == ScriptType::P2shP2wpkh {
+                0
+            } else {
+                1
+            };
+            assert_eq!(
+                found.index, expected_index,
+                "Index mismatch for {}",
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_change_address() {
+        let xpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        let change = "bc1qggnasd834t54yulsep6fta8lpjekv4zj6gv5rf";
+
+        let found = xpub
+            .find_address(change, ChainSelection::Both, range(20))
+            .unwrap()
+            .expect("change address should be found");
+        assert_eq!(found.chain, Chain::Internal, "Chain mismatch");
+        assert_eq!(found.index, 1, "Index mismatch");
+        assert_eq!(found.path().to_string(), "m/1/1", "Path mismatch");
+
+        assert!(
+            xpub.find_address(change, ChainSelection::External, range(20))
+                .unwrap()
+                .is_none(),
+            "Change addresses should not be found on the external chain"
+        );
+    }
+
+    #[test]
+    fn test_find_address_limit() {
+        let xpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        let addresses = xpub
+            .derive_bip44_address_range(
+                ChainSelection::External,
+                IndexRange::new(25, 26).unwrap(),
+                ScriptType::P2wpkh,
+            )
+            .unwrap();
+        let (_, _, address) = &addresses[0];
+
+        assert!(
+            xpub.find_address(address, ChainSelection::Both, range(25))
+                .unwrap()
+                .is_none(),
+            "Index 25 is beyond a scan of 25 indices"
+        );
+        let found = xpub
+            .find_address(address, ChainSelection::Both, range(26))
+            .unwrap()
+            .expect("address should be found within 26 indices");
+        assert_eq!(found.index, 25, "Index mismatch");
+    }
+
+    #[test]
+    fn test_find_address_case_insensitive() {
+        let xpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        let found = xpub
+            .find_address(
+                "BC1QCR8TE4KR609GCAWUTMRZA0J4XV80JY8Z306FYU",
+                ChainSelection::Both,
+                range(20),
+            )
+            .unwrap();
+        assert_eq!(
+            found.map(|found| found.index),
+            Some(0),
+            "Upper case bech32 addresses should be found"
+        );
+    }
+
+    #[test]
+    fn test_find_address_wrong_network() {
+        let xpub = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        assert!(
+            matches!(
+                xpub.find_address(
+                    "tb1qjzm2wpjl0z0gtuuu24aaqx5dejekhvmqh2l2k8",
+                    ChainSelection::Both,
+                    range(20)
+                ),
+                Err(Error::AddressNetworkMismatch { .. })
+            ),
+            "Testnet addresses should be rejected for a mainnet key"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use bip_tools::{Chain, ChainSelection, Error, IndexRange, ScriptType, Xpub};
+
+#[cfg(test)]
+mod index_range_tests {
+    use super::*;
+
+    // Same account key as the BIP44 test vectors
+    const TEST_XPUB: &str = "xpub6CQrEh7fCh2jd4kdgqCxAQ4dpzvLGCmx5PM3GLQH1bQRCLWRUMHqeZ5XWi8QUM39BeFeBJaUA5VS4Vvw5oLaA6tHZBifTetFCxj6keSvfFS";
+
+    // Known receive addresses at 0/0, 0/1 and 0/2
+    const EXPECTED_BIP44_ADDRESSES: [&str; 3] = [
+        "1AkcymbeHtiufKa1EgC1TY4E36ehdKVEDt",
+        "1BNedVV6nTX9oN77tMtoToFQ6FGQf8A3sY",
+        "176FPbVE5GScCh7jvMcj6TjBwrecs8BeAR",
+    ];
+
+    // Range Construction Tests
+
+    #[test]
+    fn test_range_bounds() {
+        let range = IndexRange::new(10_000, 10_100).unwrap();
+        assert_eq!(range.len(), 100);
+        assert_eq!(range.iter().next(), Some(10_000));
+        assert_eq!(range.iter().last(), Some(10_099));
+
+        let stepped = IndexRange::new(0, 10).unwrap().with_step(3).unwrap();
+        assert_eq!(stepped.iter().collect::<Vec<_>>(), [0, 3, 6, 9]);
+        assert_eq!(stepped.len(), 4);
+
+        assert!(IndexRange::new(5, 5).unwrap().is_empty());
+        assert_eq!(
+            IndexRange::new(0, 1).unwrap().with_step(0).unwrap_err(),
+            Error::ZeroRangeStep
+        );
+        assert_eq!(
+            IndexRange::new(6, 5).unwrap_err(),
+            Error::RangeStartAfterEnd { start: 6, end: 5 },
+            "Start after end"
+        );
+    }
+
+    #[test]
+    fn test_range_hardened_boundary() {
+        // The last non-hardened index is 2^31 - 1
+        let range = IndexRange::new(0x7FFF_FFFE, 0x8000_0000).unwrap();
+        assert_eq!(range.len(), 2);
+
+        assert_eq!(
+            IndexRange::new(0x7FFF_FFFF, 0x8000_0001).unwrap_err(),
+            Error::RangeCrossesHardened { end: 0x8000_0001 }
+        );
+
+        assert!(IndexRange::from_count(0x7FFF_FFFF, 1).is_ok());
+        assert!(IndexRange::from_count(0x7FFF_FFFF, 2).is_err());
+        assert_eq!(
+            IndexRange::from_count(u32::MAX, 1).unwrap_err(),
+            Error::RangeOverflow {
+                start: u32::MAX,
+                count: 1
+            },
+            "Overflowing count should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_range_chunks() {
+        let stepped = IndexRange::new(3, 30).unwrap().with_step(4).unwrap();
+        let chunks: Vec<IndexRange> = stepped.chunks(3).collect();
+        assert_eq!(chunks.len(), 3, "7 indices split into chunks of 3");
+        assert!(
+            chunks.iter().all(|chunk| chunk.step() == 4),
+            "Chunks keep the step"
+        );
+        assert_eq!(
+            chunks
+                .iter()
+                .flat_map(|chunk| chunk.iter())
+                .collect::<Vec<_>>(),
+            stepped.iter().collect::<Vec<_>>(),
+            "Chunks should cover the range in order"
+        );
+        assert_eq!(
+            chunks.iter().map(|chunk| chunk.len()).collect::<Vec<_>>(),
+            [3, 3, 1]
+        );
+
+        // The last chunk stops at the hardened boundary
+        let top = IndexRange::new(0x7FFF_FFF0, 0x8000_0000).unwrap();
+        let last = top.chunks(10).last().unwrap();
+        assert_eq!((last.start(), last.end()), (0x7FFF_FFFA, 0x8000_0000));
+
+        assert_eq!(IndexRange::new(5, 5).unwrap().chunks(10).count(), 0);
+    }
+
+    // Range Derivation Tests
+
+    #[test]
+    fn test_range_bip32_matches_full_derivation() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let full = xpub.derive_bip32_addresses(20).unwrap();
+
+        let range = IndexRange::new(15, 20).unwrap();
+        let partial = xpub
+            .derive_bip32_address_range(range, ScriptType::P2pkh)
+            .unwrap();
+
+        assert_eq!(partial.len(), 5);
+        for (i, address) in partial {
+            assert_eq!(address, full[i as usize], "Index {} should match", i);
+        }
+    }
+
+    #[test]
+    fn test_range_bip44_with_step() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::new(0, 3).unwrap().with_step(2).unwrap();
+        let addresses = xpub
+            .derive_bip44_address_range(ChainSelection::External, range, ScriptType::P2pkh)
+            .unwrap();
+
+        assert_eq!(
+            addresses,
+            [
+                (Chain::External, 0, EXPECTED_BIP44_ADDRESSES[0].to_string()),
+                (Chain::External, 2, EXPECTED_BIP44_ADDRESSES[2].to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_bip44_both_chains() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::new(1, 3).unwrap();
+        let addresses = xpub
+            .derive_bip44_address_range(ChainSelection::Both, range, ScriptType::P2pkh)
+            .unwrap();
+
+        let indices: Vec<(Chain, u32)> = addresses.iter().map(|(c, i, _)| (*c, *i)).collect();
+        assert_eq!(
+            indices,
+            [
+                (Chain::External, 1),
+                (Chain::External, 2),
+                (Chain::Internal, 1),
+                (Chain::Internal, 2),
+            ]
+        );
+        assert_eq!(addresses[0].2, EXPECTED_BIP44_ADDRESSES[1]);
+    }
+
+    #[test]
+    fn test_range_last_non_hardened_index() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::from_count(0x7FFF_FFFF, 1).unwrap();
+        let addresses = xpub
+            .derive_bip32_address_range(range, ScriptType::P2pkh)
+            .unwrap();
+
+        assert_eq!(addresses.len(), 1);
+        assert_eq!(addresses[0].0, 0x7FFF_FFFF);
+    }
+}
@@ -0,0 +1,189 @@
+use base58::{FromBase58, ToBase58};
+use bip_tools::output::Value;
+use bip_tools::{Error, KeyInfo, MultisigType, Network, ScriptType, Version, Xpub};
+
+#[cfg(test)]
+mod inspect_tests {
+    use super::*;
+
+    // BIP32 test vector 1: chain m and chain m/0H
+    const MASTER_XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+    const CHILD_XPUB: &str = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw";
+    const MASTER_XPRV: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+    // BIP84 account key m/84'/0'/0'
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Re-encodes the key with the last checksum byte flipped
+    fn corrupt_checksum(key: &str) -> String {
+        let mut decoded = key.from_base58().unwrap();
+        *decoded.last_mut().unwrap() ^= 0x01;
+        decoded.to_base58()
+    }
+
+    // Decoded Field Tests
+
+    #[test]
+    fn test_inspect_master_key() {
+        let info = KeyInfo::from_base58(MASTER_XPUB).unwrap();
+        assert_eq!(info.version, Version::Xpub);
+        assert_eq!(info.network, Network::Bitcoin);
+        assert_eq!(info.script_type, ScriptType::P2pkh);
+        assert_eq!(info.multisig_type, None);
+        assert_eq!(info.depth, 0);
+        assert_eq!(info.parent_fingerprint, 0);
+        assert_eq!(info.child_number, 0);
+        assert!(!info.is_hardened());
+        assert_eq!(
+            hex(&info.chain_code),
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508"
+        );
+        assert_eq!(
+            hex(&info.public_key.serialize()),
+            "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2"
+        );
+        assert_eq!(info.fingerprint, 0x3442193e, "Master fingerprint mismatch");
+        assert!(info.checksum_valid);
+        assert_eq!(
+            Xpub::from_base58(MASTER_XPUB).unwrap().public_key_hex(),
+            hex(&info.public_key.serialize()),
+            "The Xpub should format the same public key"
+        );
+    }
+
+    #[test]
+    fn test_inspect_hardened_child() {
+        let info = KeyInfo::from_base58(CHILD_XPUB).unwrap();
+        assert_eq!(info.depth, 1);
+        assert_eq!(info.parent_fingerprint, 0x3442193e);
+        assert_eq!(info.child_number, 0x80000000);
+        assert!(info.is_hardened(), "m/0H is a hardened child");
+        assert_eq!(
+            hex(&info.chain_code),
+            "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141"
+        );
+        assert_eq!(
+            hex(&info.public_key.serialize()),
+            "035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56"
+        );
+        assert_eq!(info.fingerprint, 0x5c1bd648, "Child fingerprint mismatch");
+    }
+
+    #[test]
+    fn test_inspect_slip132_versions() {
+        let info = KeyInfo::from_base58(BIP84_ZPUB).unwrap();
+        assert_eq!(info.version, Version::Zpub);
+        assert_eq!(info.script_type_name(), "p2wpkh");
+
+        let multisig = Xpub::from_base58(BIP84_ZPUB)
+            .unwrap()
+            .with_version(Version::VpubMultisig);
+        let info = KeyInfo::from_base58(&multisig.to_base58()).unwrap();
+        assert_eq!(info.network, Network::Testnet);
+        assert_eq!(info.multisig_type, Some(MultisigType::P2wsh));
+        assert_eq!(
+            info.script_type_name(),
+            "p2wsh",
+            "Multisig versions imply a multisig script type"
+        );
+    }
+
+    #[test]
+    fn test_inspect_matches_parsed_key() {
+        for key in [MASTER_XPUB, CHILD_XPUB, BIP84_ZPUB] {
+            let xpub = Xpub::from_base58(key).unwrap();
+            assert_eq!(
+                KeyInfo::from_base58(key).unwrap(),
+                KeyInfo::from_xpub(&xpub),
+                "Decoded and parsed keys should describe the same fields"
+            );
+        }
+    }
+
+    // Checksum and Error Tests
+
+    #[test]
+    fn test_inspect_bad_checksum() {
+        let corrupted = corrupt_checksum(CHILD_XPUB);
+        assert!(matches!(
+            Xpub::from_base58(&corrupted),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+
+        let info = KeyInfo::from_base58(&corrupted).unwrap();
+        assert!(!info.checksum_valid, "Checksum should be reported invalid");
+        assert_eq!(
+            KeyInfo {
+                checksum_valid: true,
+                ..info
+            },
+            KeyInfo::from_base58(CHILD_XPUB).unwrap(),
+            "Fields should still be decoded"
+        );
+    }
+
+    #[test]
+    fn test_inspect_rejects_undecodable_keys() {
+        assert_eq!(
+            KeyInfo::from_base58(MASTER_XPRV).unwrap_err(),
+            Error::PrivateKeyVersion("xprv")
+        );
+        assert_eq!(
+            KeyInfo::from_base58(&MASTER_XPUB[..100]).unwrap_err(),
+            Error::InvalidLength {
+                expected: 82,
+                found: 74
+            }
+        );
+    }
+
+    // Output Tests
+
+    #[test]
+    fn test_inspect_record() {
+        let record = KeyInfo::from_base58(CHILD_XPUB).unwrap().to_record();
+        let names: Vec<&str> = record.fields().iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            [
+                "version",
+                "version_bytes",
+                "network",
+                "script_type",
+                "multisig",
+                "depth",
+                "parent_fingerprint",
+                "child_number",
+                "hardened",
+                "chain_code",
+                "public_key",
+                "fingerprint",
+                "checksum_valid"
+            ],
+            "Record field order mismatch"
+        );
+        assert_eq!(record.get("version_bytes"), Some(&Value::from("0488b21e")));
+        assert_eq!(record.get("child_number"), Some(&Value::Number(0x80000000)));
+        assert_eq!(record.get("hardened"), Some(&Value::Bool(true)));
+        assert_eq!(record.get("fingerprint"), Some(&Value::from("5c1bd648")));
+        assert!(record.to_json().contains("\"checksum_valid\":true"));
+    }
+
+    #[test]
+    fn test_inspect_text() {
+        let text = KeyInfo::from_base58(CHILD_XPUB).unwrap().to_string();
+        assert!(text.starts_with("Version:            xpub (0x0488B21E)\n"));
+        assert!(text.contains("Child number:       2147483648 (0', hardened)\n"));
+        assert!(text.ends_with("Checksum:           valid"));
+
+        let text = KeyInfo::from_base58(&corrupt_checksum(MASTER_XPUB))
+            .unwrap()
+            .to_string();
+        assert!(text.contains("Depth:              0 (master key)\n"));
+        assert!(text.ends_with("Checksum:           INVALID"));
+    }
+}
@@ -0,0 +1,163 @@
+use bip_tools::{
+    DerivationPath, Descriptor, IndexRange, KeyOrigin, Mnemonic, Purpose, ScriptType, Version,
+    Xprv, Xpub,
+};
+
+#[cfg(test)]
+mod key_origin_tests {
+    use super::*;
+
+    // "abandon ... about" mnemonic, its master fingerprint and BIP84 account key
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const MASTER_FINGERPRINT: u32 = 0x73c5da0a;
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    fn master() -> Xprv {
+        Mnemonic::parse(MNEMONIC)
+            .unwrap()
+            .to_master_key("")
+            .unwrap()
+    }
+
+    fn origin(s: &str) -> KeyOrigin {
+        s.parse().unwrap()
+    }
+
+    fn path(s: &str) -> DerivationPath {
+        s.parse().unwrap()
+    }
+
+    // Key Origin Tests
+
+    #[test]
+    fn test_key_origin_parsing() {
+        assert_eq!(
+            origin("[73c5da0a/84'/0'/0']"),
+            origin("73c5da0a/84h/0h/0h"),
+            "Brackets should be optional"
+        );
+        assert_eq!(origin("73c5da0a"), KeyOrigin::master(MASTER_FINGERPRINT));
+        assert_eq!(
+            origin("73c5da0a/84'/0'/0'").child(1).to_string(),
+            "73c5da0a/84'/0'/0'/1"
+        );
+        assert!("[73c5da0a/84'/0'/0'".parse::<KeyOrigin>().is_err());
+    }
+
+    // Master Key Tests
+
+    #[test]
+    fn test_master_key_is_its_own_origin() {
+        let master = master().to_xpub();
+        assert!(master.origin.is_none());
+        assert_eq!(
+            master.key_origin(),
+            Some(KeyOrigin::master(MASTER_FINGERPRINT)),
+            "A master key should be its own origin"
+        );
+
+        let child = master.derive_path(&path("0/5")).unwrap();
+        assert_eq!(child.key_origin(), Some(origin("73c5da0a/0/5")));
+    }
+
+    #[test]
+    fn test_private_derivation_tracks_origin() {
+        let account = master().derive_account(Purpose::Bip84, 0).unwrap();
+        assert_eq!(account.origin, Some(origin("73c5da0a/84'/0'/0'")));
+
+        let xpub = account.to_xpub();
+        assert_eq!(xpub.to_base58(), BIP84_ZPUB);
+        assert_eq!(
+            xpub.key_origin(),
+            Some(origin("73c5da0a/84'/0'/0'")),
+            "The public key should keep the origin of the private key"
+        );
+
+        let private_child = master()
+            .derive_path(&path("m/84'/0'/0'/1/7"))
+            .unwrap()
+            .to_xpub();
+        let public_child = xpub.derive_path(&path("1/7")).unwrap();
+        assert_eq!(private_child.origin, public_child.origin);
+        assert_eq!(private_child.public_key, public_child.public_key);
+    }
+
+    // Parsed and Attached Origin Tests
+
+    #[test]
+    fn test_parsed_key_has_no_origin() {
+        let account = Xpub::from_base58(BIP84_ZPUB).unwrap();
+        assert!(account.key_origin().is_none());
+        assert!(
+            account
+                .derive_non_hardened(0)
+                .unwrap()
+                .key_origin()
+                .is_none(),
+            "Children of a key without origin should not get one"
+        );
+    }
+
+    #[test]
+    fn test_attached_origin_is_extended() {
+        let account = Xpub::from_base58(BIP84_ZPUB)
+            .unwrap()
+            .with_origin(origin("73c5da0a/84'/0'/0'"));
+
+        let child = account
+            .derive_non_hardened(0)
+            .unwrap()
+            .derive_non_hardened(5)
+            .unwrap();
+        assert_eq!(child.key_origin(), Some(origin("73c5da0a/84'/0'/0'/0/5")));
+        assert_eq!(
+            account.derive_path(&path("0/5")).unwrap().origin,
+            child.origin,
+            "Path derivation should match step by step derivation"
+        );
+        assert_eq!(
+            account.convert(Version::Xpub).xpub.key_origin(),
+            account.key_origin(),
+            "Conversion should keep the origin"
+        );
+    }
+
+    #[test]
+    fn test_address_iter_tracks_origin() {
+        let account = Xpub::from_base58(BIP84_ZPUB)
+            .unwrap()
+            .with_origin(origin("73c5da0a/84'/0'/0'"));
+        let receive = account.derive_non_hardened(0).unwrap();
+
+        let range = IndexRange::from_count(3, 2).unwrap();
+        for (i, child, _) in receive.address_iter(range, ScriptType::P2wpkh).unwrap() {
+            assert_eq!(
+                child.origin.unwrap().to_string(),
+                format!("73c5da0a/84'/0'/0'/0/{}", i)
+            );
+        }
+    }
+
+    // Descriptor Key Tests
+
+    #[test]
+    fn test_descriptor_key_origin() {
+        let descriptor: Descriptor = format!("wpkh([73c5da0a/84'/0'/0']{}/1/*)", BIP84_ZPUB)
+            .parse()
+            .unwrap();
+        let key = descriptor.key();
+
+        assert_eq!(
+            key.derive(9).unwrap().key_origin(),
+            Some(origin("73c5da0a/84'/0'/0'/1/9")),
+            "Derived keys should carry the descriptor key origin"
+        );
+        assert_eq!(
+            key.derive(9).unwrap().origin.unwrap().path,
+            key.full_path().child(9)
+        );
+
+        let bare: Descriptor = format!("wpkh({}/1/*)", BIP84_ZPUB).parse().unwrap();
+        assert!(bare.key().derive(9).unwrap().key_origin().is_none());
+    }
+}
@@ -0,0 +1,274 @@
+use bip_tools::{
+    DerivationPath, Error, IndexRange, Multisig, MultisigType, Network, Version, Xpub,
+};
+use secp256k1::PublicKey;
+
+#[cfg(test)]
+mod multisig_tests {
+    use super::*;
+
+    const XPUB_A: &str = "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB";
+    const XPUB_B: &str = "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH";
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Wraps a bare public key in a depth 0 Xpub, so it is used without derivation
+    fn key(hex: &str) -> Xpub {
+        let public_key = PublicKey::from_slice(&from_hex(hex)).unwrap();
+        Xpub::new(0, 0, 0, [0u8; 32], public_key)
+    }
+
+    fn xpub(xpub: &str) -> Xpub {
+        Xpub::from_base58(xpub).unwrap()
+    }
+
+    // BIP67 Key Sorting Tests
+
+    #[test]
+    fn test_bip67_vectors() {
+        // (public keys in given order, sorted redeem script, P2SH address) from BIP67
+        let vectors = [
+            (
+                vec![
+                    "02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8",
+                    "02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f",
+                ],
+                "522102fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f2102ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f852ae",
+                "39bgKC7RFbpoCRbtD5KEdkYKtNyhpsNa3Z",
+            ),
+            (
+                vec![
+                    "02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0",
+                    "027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77",
+                    "02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404",
+                ],
+                "522102632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed021027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e772102e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b40453ae",
+                "3CKHTjBKxCARLzwABMu9yD85kvtm7WnMfH",
+            ),
+            (
+                vec![
+                    "030000000000000000000000000000000000004141414141414141414141414141",
+                    "020000000000000000000000000000000000004141414141414141414141414141",
+                    "020000000000000000000000000000000000004141414141414141414141414140",
+                    "030000000000000000000000000000000000004141414141414141414141414140",
+                ],
+                "522102000000000000000000000000000000000000414141414141414141414141414021020000000000000000000000000000000000004141414141414141414141414141210300000000000000000000000000000000000041414141414141414141414141402103000000000000000000000000000000000000414141414141414141414141414154ae",
+                "32V85igBri9zcfBRVupVvwK18NFtS37FuD",
+            ),
+        ];
+
+        for (keys, script, address) in vectors {
+            let multisig = Multisig::new(2, keys.iter().map(|k| key(k)).collect()).unwrap();
+            assert_eq!(to_hex(&multisig.script()), script, "Script mismatch");
+            assert_eq!(
+                multisig.to_address(MultisigType::P2sh).unwrap(),
+                address,
+                "P2SH address mismatch"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsorted_multi() {
+        let keys = vec![
+            key("02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8"),
+            key("02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f"),
+        ];
+        let multisig = Multisig::new(2, keys).unwrap().with_sorted(false);
+        assert_eq!(
+            to_hex(&multisig.script()),
+            "522102ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f82102fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f52ae",
+            "multi should keep the cosigner order"
+        );
+    }
+
+    // Address Type Tests
+
+    #[test]
+    fn test_sortedmulti_p2sh() {
+        let keys = vec![
+            key("03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556"),
+            key("0250863ad64a87ae8a2fe83c1af1a8403cb53f53e486d8511dad8a04887e5b2352"),
+        ];
+        let multisig = Multisig::new(1, keys).unwrap();
+        assert_eq!(
+            multisig.to_address(MultisigType::P2sh).unwrap(),
+            "3JZJNxvDKe6Y55ZaF5223XHwfF2eoMNnoV",
+            "sh(sortedmulti) address mismatch"
+        );
+    }
+
+    #[test]
+    fn test_sortedmulti_p2wsh() {
+        for xpubs in [[XPUB_A, XPUB_B], [XPUB_B, XPUB_A]] {
+            let multisig = Multisig::new(1, xpubs.iter().map(|x| xpub(x)).collect()).unwrap();
+            assert_eq!(
+                multisig.to_address(MultisigType::P2wsh).unwrap(),
+                "bc1qpq2cfgz5lktxzr5zqv7nrzz46hsvq3492ump9pz8rzcl8wqtwqcspx5y6a",
+                "wsh(sortedmulti) address should not depend on key order"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sortedmulti_p2sh_p2wsh() {
+        // Cosigners at xpub_a/1/0/5 and xpub_b/0/0/5
+        let xpub_a = xpub(XPUB_A).derive_path(&"1/0".parse().unwrap()).unwrap();
+        let xpub_b = xpub(XPUB_B).derive_path(&"0/0".parse().unwrap()).unwrap();
+        let multisig = Multisig::new(1, vec![xpub_a, xpub_b]).unwrap();
+
+        let child = multisig.derive_non_hardened(5).unwrap();
+        assert_eq!(
+            child.to_address(MultisigType::P2shP2wsh).unwrap(),
+            "325zcVBN5o2eqqqtGwPjmtDd8dJRyYP82s",
+            "sh(wsh(sortedmulti)) address mismatch"
+        );
+
+        let range = IndexRange::new(5, 6).unwrap();
+        let addresses = multisig
+            .derive_address_range(&DerivationPath::default(), range, MultisigType::P2shP2wsh)
+            .unwrap();
+        let addresses: Vec<(u32, String)> = addresses
+            .into_iter()
+            .map(|(i, _, address)| (i, address))
+            .collect();
+        assert_eq!(
+            addresses,
+            vec![(5, "325zcVBN5o2eqqqtGwPjmtDd8dJRyYP82s".to_string())],
+            "Range address mismatch"
+        );
+    }
+
+    #[test]
+    fn test_multisig_path_range() {
+        let multisig = Multisig::new(2, vec![xpub(XPUB_A), xpub(XPUB_B)]).unwrap();
+        let path: DerivationPath = "0/*".parse().unwrap();
+        let addresses = multisig
+            .derive_address_range(&path, IndexRange::new(0, 3).unwrap(), MultisigType::P2wsh)
+            .unwrap();
+
+        for (i, child, address) in addresses {
+            let expected = multisig.derive_path(&path.child(i)).unwrap();
+            assert_eq!(
+                address,
+                expected.to_address(MultisigType::P2wsh).unwrap(),
+                "Address mismatch at index {}",
+                i
+            );
+            assert_eq!(
+                child.script(),
+                expected.script(),
+                "Script mismatch at index {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_multisig_network() {
+        let multisig = Multisig::new(1, vec![xpub(XPUB_A), xpub(XPUB_B)])
+            .unwrap()
+            .with_network(Network::Signet);
+        assert!(
+            matches!(multisig, Err(Error::NetworkMismatch { .. })),
+            "Test networks should be rejected for mainnet keys"
+        );
+
+        let mixed = vec![xpub(XPUB_A), xpub(XPUB_B).with_version(Version::Tpub)];
+        assert!(
+            matches!(Multisig::new(1, mixed), Err(Error::InvalidMultisig(msg)) if msg.contains("networks")),
+            "Cosigners on different networks should be rejected"
+        );
+    }
+
+    // Invalid Policy Tests
+
+    #[test]
+    fn test_multisig_invalid_threshold() {
+        for threshold in [0, 3] {
+            assert!(
+                matches!(
+                    Multisig::new(threshold, vec![xpub(XPUB_A), xpub(XPUB_B)]),
+                    Err(Error::InvalidMultisig(_))
+                ),
+                "Threshold {} should be rejected for 2 keys",
+                threshold
+            );
+        }
+        assert!(
+            matches!(Multisig::new(1, Vec::new()), Err(Error::InvalidMultisig(_))),
+            "A multisig needs at least one key"
+        );
+    }
+
+    #[test]
+    fn test_multisig_duplicate_keys() {
+        let result = Multisig::new(2, vec![xpub(XPUB_A), xpub(XPUB_B), xpub(XPUB_A)]);
+        assert!(
+            matches!(result, Err(Error::InvalidMultisig(msg)) if msg.contains("more than once")),
+            "A cosigner key given twice should be rejected"
+        );
+
+        // The same public key under another version is still the same cosigner
+        let converted = xpub(XPUB_A).with_version(Version::Zpub);
+        assert!(
+            matches!(
+                Multisig::new(1, vec![xpub(XPUB_A), converted]),
+                Err(Error::InvalidMultisig(_))
+            ),
+            "Keys are compared by their public key"
+        );
+    }
+
+    #[test]
+    fn test_multisig_p2sh_size_limit() {
+        let parent = xpub(XPUB_A);
+        let keys: Vec<Xpub> = (0..16)
+            .map(|i| parent.derive_non_hardened(i).unwrap())
+            .collect();
+
+        let multisig = Multisig::new(2, keys[..15].to_vec()).unwrap();
+        assert!(
+            multisig.to_address(MultisigType::P2sh).is_ok(),
+            "15 keys fit in a P2SH redeem script"
+        );
+
+        let multisig = Multisig::new(2, keys).unwrap();
+        assert!(
+            matches!(
+                multisig.to_address(MultisigType::P2sh),
+                Err(Error::InvalidMultisig(_))
+            ),
+            "16 keys exceed the P2SH redeem script limit"
+        );
+        assert!(
+            multisig.to_address(MultisigType::P2wsh).is_ok(),
+            "16 keys are allowed in P2WSH"
+        );
+    }
+
+    #[test]
+    fn test_multisig_type_from_str() {
+        for multisig_type in MultisigType::ALL {
+            assert_eq!(
+                multisig_type.name().parse::<MultisigType>().unwrap(),
+                multisig_type,
+                "Name should round-trip"
+            );
+        }
+        assert_eq!(
+            "p2tr".parse::<MultisigType>().err(),
+            Some(Error::UnknownMultisigType("p2tr".to_string())),
+            "Unknown types should be rejected"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use base58::{FromBase58, ToBase58};
+use bip_tools::{Error, Network, Version, Xpub};
+use sha2::{Digest, Sha256};
+
+#[cfg(test)]
+mod network_tests {
+    use super::*;
+
+    // Same key material as the BIP32 test vectors
+    const TEST_XPUB: &str = "xpub681vrYy1g8k1xtcNPi2WN9pGiHDejoCvUT4GG2Mbs9rcs98VWvoQXmgT2J1umYQs9p2qp6xdMjJ2AU1rNcCMq9RmtKNhowJKYvVgKwS59xX";
+
+    // First mainnet P2PKH address derived from TEST_XPUB
+    const MAINNET_ADDRESS: &str = "1FvSF5syVSTnsbNzFpPd4mNFcSvwtTxqLw";
+
+    // Helper function to re-encode Base58Check data with another leading version
+    fn replace_version(encoded: &str, version: &[u8]) -> String {
+        let mut data = encoded.from_base58().unwrap();
+        data[..version.len()].copy_from_slice(version);
+        let payload_len = data.len() - 4;
+        let checksum = Sha256::digest(Sha256::digest(&data[..payload_len]));
+        data[payload_len..].copy_from_slice(&checksum[..4]);
+        data.to_base58()
+    }
+
+    fn test_tpub() -> String {
+        replace_version(TEST_XPUB, &Version::Tpub.to_bytes())
+    }
+
+    #[test]
+    fn test_network_parse_names() {
+        for network in Network::ALL {
+            assert_eq!(
+                network.name().parse::<Network>().unwrap(),
+                network,
+                "Network name should round-trip"
+            );
+        }
+        assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Bitcoin);
+        assert_eq!("testnet3".parse::<Network>().unwrap(), Network::Testnet);
+        assert!("litecoin".parse::<Network>().is_err());
+    }
+
+    #[test]
+    fn test_network_prefixes() {
+        assert_eq!(Network::Bitcoin.p2pkh_prefix(), 0x00);
+        assert_eq!(Network::Bitcoin.p2sh_prefix(), 0x05);
+        assert_eq!(Network::Bitcoin.bech32_hrp(), "bc");
+
+        for network in [Network::Testnet, Network::Testnet4, Network::Signet] {
+            assert_eq!(network.p2pkh_prefix(), 0x6F);
+            assert_eq!(network.p2sh_prefix(), 0xC4);
+            assert_eq!(network.bech32_hrp(), "tb");
+        }
+
+        assert_eq!(Network::Regtest.p2pkh_prefix(), 0x6F);
+        assert_eq!(Network::Regtest.bech32_hrp(), "bcrt");
+    }
+
+    #[test]
+    fn test_network_inferred_from_version() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        assert_eq!(xpub.network, Network::Bitcoin, "xpub should be mainnet");
+
+        let tpub = Xpub::from_base58(&test_tpub()).unwrap();
+        assert_eq!(tpub.network, Network::Testnet, "tpub should be testnet");
+    }
+
+    #[test]
+    fn test_network_testnet_address() {
+        // Same HASH160 as the mainnet address, with the 0x6F prefix
+        let tpub = Xpub::from_base58(&test_tpub()).unwrap();
+        let address = tpub.derive_bip32_addresses(1).unwrap();
+
+        assert_eq!(address[0], replace_version(MAINNET_ADDRESS, &[0x6F]));
+        assert!(
+            address[0].starts_with('m') || address[0].starts_with('n'),
+            "Testnet P2PKH address should start with m or n"
+        );
+    }
+
+    #[test]
+    fn test_network_override() {
+        let tpub = Xpub::from_base58(&test_tpub()).unwrap();
+
+        for network in [Network::Testnet4, Network::Signet, Network::Regtest] {
+            let xpub = tpub.clone().with_network(network).unwrap();
+            assert_eq!(xpub.network, network, "Network should be overridden");
+            assert_eq!(
+                xpub.derive_non_hardened(0).unwrap().network,
+                network,
+                "Children should keep the network"
+            );
+            assert_eq!(xpub.to_base58(), test_tpub(), "Serialization is unchanged");
+        }
+    }
+
+    #[test]
+    fn test_network_mismatch_rejected() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let result = xpub.with_network(Network::Regtest);
+        assert!(result.is_err(), "xpub should not be used on regtest");
+        assert_eq!(
+            result.err(),
+            Some(Error::NetworkMismatch {
+                network: Network::Regtest,
+                version: Version::Xpub
+            })
+        );
+
+        let tpub = Xpub::from_base58(&test_tpub()).unwrap();
+        assert!(tpub.with_network(Network::Bitcoin).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use bip_tools::output::Value;
+use bip_tools::{Error, Format, Record, RecordWriter};
+
+#[cfg(test)]
+mod output_tests {
+    use super::*;
+
+    fn address_record(index: u32) -> Record {
+        Record::new()
+            .with("index", index)
+            .with("path", format!("m/0/{}", index))
+            .with("address", "1AkcymbeHtiufKa1EgC1TY4E36ehdKVEDt")
+    }
+
+    /// Writes the records in the given format and returns the output
+    fn render(format: Format, records: &[Record]) -> String {
+        let mut out = Vec::new();
+        let mut writer = RecordWriter::new(&mut out, format);
+        writer.note("Generating addresses:").unwrap();
+        for record in records {
+            writer
+                .write(record, || format!("Child {}", record.get("index").unwrap()))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    // Format and Record Tests
+
+    #[test]
+    fn test_format_names() {
+        for format in Format::ALL {
+            assert_eq!(
+                format.name().parse::<Format>().unwrap(),
+                format,
+                "Format name should round trip"
+            );
+        }
+        assert_eq!("JSONL".parse::<Format>().unwrap(), Format::Ndjson);
+        assert_eq!(Format::default(), Format::Text);
+        assert!(!Format::Text.is_machine_readable());
+        assert!(
+            matches!("yaml".parse::<Format>(), Err(Error::UnknownFormat(name)) if name == "yaml"),
+            "Unknown formats should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_record_json() {
+        let record = Record::new()
+            .with("index", 5u32)
+            .with("label", "say \"hi\"\\\n")
+            .with("public_key", None::<String>);
+        assert_eq!(
+            record.to_json(),
+            r#"{"index":5,"label":"say \"hi\"\\\n","public_key":null}"#,
+            "JSON object mismatch"
+        );
+        assert_eq!(record.get("index"), Some(&Value::Number(5)));
+        assert_eq!(record.get("missing"), None);
+        assert_eq!(Record::new().to_json(), "{}");
+    }
+
+    // Record Writer Tests
+
+    #[test]
+    fn test_writer_text() {
+        let output = render(Format::Text, &[address_record(0), address_record(1)]);
+        assert_eq!(
+            output, "Generating addresses:\nChild 0\nChild 1\n",
+            "Text output should contain the notes and caller phrased lines"
+        );
+    }
+
+    #[test]
+    fn test_writer_json() {
+        let output = render(Format::Json, &[address_record(0), address_record(1)]);
+        assert_eq!(
+            output,
+            concat!(
+                "[\n",
+                "  {\"index\":0,\"path\":\"m/0/0\",\"address\":\"1AkcymbeHtiufKa1EgC1TY4E36ehdKVEDt\"},\n",
+                "  {\"index\":1,\"path\":\"m/0/1\",\"address\":\"1AkcymbeHtiufKa1EgC1TY4E36ehdKVEDt\"}\n",
+                "]\n"
+            ),
+            "JSON output should be an array without the notes"
+        );
+        assert_eq!(render(Format::Json, &[]), "[]\n", "Empty JSON array");
+    }
+
+    #[test]
+    fn test_writer_ndjson() {
+        let output = render(Format::Ndjson, &[address_record(0), address_record(1)]);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2, "One line per record");
+        assert_eq!(lines[1], address_record(1).to_json());
+        assert_eq!(render(Format::Ndjson, &[]), "", "No records, no output");
+    }
+
+    #[test]
+    fn test_writer_csv() {
+        let records = [
+            address_record(0),
+            Record::new()
+                .with("address", "a,\"b\"")
+                .with("index", 1u32)
+                .with("extra", "ignored"),
+        ];
+        assert_eq!(
+            render(Format::Csv, &records),
+            concat!(
+                "index,path,address\n",
+                "0,m/0/0,1AkcymbeHtiufKa1EgC1TY4E36ehdKVEDt\n",
+                "1,,\"a,\"\"b\"\"\"\n"
+            ),
+            "CSV rows should follow the columns of the first record"
+        );
+    }
+
+    #[test]
+    fn test_writer_notes() {
+        let (mut out, mut notes) = (Vec::new(), Vec::new());
+        let mut writer = RecordWriter::new(&mut out, Format::Ndjson).with_notes(&mut notes);
+        writer.note("Generating addresses:").unwrap();
+        writer.write(&address_record(0), String::new).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(notes).unwrap(),
+            "Generating addresses:\n",
+            "Machine readable notes should go to the notes writer"
+        );
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 1);
+
+        let mut notes = Vec::new();
+        let mut writer = RecordWriter::new(Vec::new(), Format::Text).with_notes(&mut notes);
+        writer.note("Generating addresses:").unwrap();
+        writer.finish().unwrap();
+        assert!(notes.is_empty(), "Text notes should stay in the output");
+    }
+}
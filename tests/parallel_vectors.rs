@@ -0,0 +1,110 @@
+#![cfg(feature = "parallel")]
+
+use bip_tools::engine::Engine;
+use bip_tools::{ChainSelection, Error, IndexRange, ScriptType, Xpub};
+
+#[cfg(test)]
+mod parallel_tests {
+    use super::*;
+
+    // Same account key as the BIP44 test vectors
+    const TEST_XPUB: &str = "xpub6CQrEh7fCh2jd4kdgqCxAQ4dpzvLGCmx5PM3GLQH1bQRCLWRUMHqeZ5XWi8QUM39BeFeBJaUA5VS4Vvw5oLaA6tHZBifTetFCxj6keSvfFS";
+
+    fn pool(threads: usize) -> rayon::ThreadPool {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap()
+    }
+
+    // Parallel and Sequential Output Tests
+
+    #[test]
+    fn test_parallel_bip32_matches_sequential() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::new(100, 600).unwrap();
+
+        for script_type in ScriptType::ALL {
+            let sequential = xpub.derive_bip32_address_range(range, script_type).unwrap();
+            for threads in [1, 3, 8] {
+                let parallel = pool(threads)
+                    .install(|| xpub.par_derive_bip32_address_range(range, script_type))
+                    .unwrap();
+                assert_eq!(
+                    parallel, sequential,
+                    "{} output differs on {} threads",
+                    script_type, threads
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_bip44_matches_sequential() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::new(7, 600).unwrap().with_step(3).unwrap();
+
+        for selection in [
+            ChainSelection::External,
+            ChainSelection::Internal,
+            ChainSelection::Both,
+        ] {
+            let sequential = xpub
+                .derive_bip44_address_range(selection, range, ScriptType::P2wpkh)
+                .unwrap();
+            let parallel = pool(4)
+                .install(|| {
+                    xpub.par_derive_bip44_address_range(selection, range, ScriptType::P2wpkh)
+                })
+                .unwrap();
+            assert_eq!(parallel, sequential, "Output differs for {:?}", selection);
+        }
+    }
+
+    #[test]
+    fn test_parallel_chunks_match_sequential() {
+        // Chunked output, as printed by the CLI, is the sequential output in order
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let range = IndexRange::new(0, 700).unwrap();
+        let pool = pool(4);
+
+        let chunked: Vec<(u32, String)> = range
+            .chunks(256)
+            .flat_map(|chunk| {
+                pool.install(|| xpub.par_derive_bip32_address_range(chunk, ScriptType::P2tr))
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(
+            chunked,
+            xpub.derive_bip32_address_range(range, ScriptType::P2tr)
+                .unwrap(),
+            "Chunked parallel output differs"
+        );
+    }
+
+    #[test]
+    fn test_parallel_engine() {
+        let xpub = Xpub::from_base58(TEST_XPUB).unwrap();
+        let engine = Engine::verification_only();
+        let range = IndexRange::new(0, 100).unwrap();
+
+        assert_eq!(
+            engine
+                .par_address_range(&xpub, range, ScriptType::P2shP2wpkh)
+                .unwrap(),
+            xpub.derive_bip32_address_range(range, ScriptType::P2shP2wpkh)
+                .unwrap(),
+            "Caller owned engine output differs"
+        );
+
+        let deepest = Xpub::new(255, 0, 0, xpub.chain_code, xpub.public_key);
+        assert!(
+            matches!(
+                engine.par_address_range(&deepest, range, ScriptType::P2pkh),
+                Err(Error::DepthOverflow)
+            ),
+            "Children of a depth 255 key cannot be derived"
+        );
+    }
+}
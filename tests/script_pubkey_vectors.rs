@@ -0,0 +1,127 @@
+use bip_tools::descriptor::DescriptorKey;
+use bip_tools::{base58check, bech32};
+use bip_tools::{Multisig, MultisigType, Network, ScriptType, Xpub};
+
+#[cfg(test)]
+mod script_pubkey_tests {
+    use super::*;
+
+    // Receive key m/84'/0'/0'/0/0 of the "abandon ... about" mnemonic
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    // BIP86 account key and the scriptPubKey of m/86'/0'/0'/0/0 from the BIP86 test vectors
+    const BIP86_XPUB: &str = "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ";
+    const BIP86_SCRIPT_PUBKEY: &str =
+        "5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c";
+
+    // Same account key as the BIP44 test vectors
+    const TEST_XPUB: &str = "xpub6CQrEh7fCh2jd4kdgqCxAQ4dpzvLGCmx5PM3GLQH1bQRCLWRUMHqeZ5XWi8QUM39BeFeBJaUA5VS4Vvw5oLaA6tHZBifTetFCxj6keSvfFS";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn receive_key(xpub: &str) -> Xpub {
+        Xpub::from_base58(xpub)
+            .unwrap()
+            .derive_path(&"0/0".parse().unwrap())
+            .unwrap()
+    }
+
+    // Single Key Script Tests
+
+    #[test]
+    fn test_p2tr_script_pubkey() {
+        let key = receive_key(BIP86_XPUB);
+        assert_eq!(
+            hex(&key.script_pubkey(ScriptType::P2tr)),
+            BIP86_SCRIPT_PUBKEY,
+            "BIP86 scriptPubKey mismatch"
+        );
+    }
+
+    #[test]
+    fn test_segwit_script_pubkey_matches_address() {
+        let key = receive_key(BIP84_ZPUB);
+        for (script_type, version) in [(ScriptType::P2wpkh, 0x00), (ScriptType::P2tr, 0x51)] {
+            let address = key.to_address(script_type);
+            let (_, program) = bech32::decode_segwit_address("bc", &address).unwrap();
+
+            let script = key.script_pubkey(script_type);
+            assert_eq!(script[0], version, "Witness version opcode mismatch");
+            assert_eq!(script[1] as usize, program.len(), "Push length mismatch");
+            assert_eq!(&script[2..], program, "Witness program mismatch");
+        }
+    }
+
+    #[test]
+    fn test_base58_script_pubkey_matches_address() {
+        let key = receive_key(TEST_XPUB);
+
+        let payload = base58check::decode_check(&key.to_address(ScriptType::P2pkh)).unwrap();
+        assert_eq!(
+            hex(&key.script_pubkey(ScriptType::P2pkh)),
+            format!("76a914{}88ac", hex(&payload[1..])),
+            "P2PKH scriptPubKey mismatch"
+        );
+
+        let payload = base58check::decode_check(&key.to_address(ScriptType::P2shP2wpkh)).unwrap();
+        assert_eq!(
+            hex(&key.script_pubkey(ScriptType::P2shP2wpkh)),
+            format!("a914{}87", hex(&payload[1..])),
+            "P2SH-P2WPKH scriptPubKey mismatch"
+        );
+    }
+
+    // Multisig Script Tests
+
+    #[test]
+    fn test_multisig_script_pubkey_matches_address() {
+        let xpubs = vec![
+            Xpub::from_base58(TEST_XPUB).unwrap(),
+            Xpub::from_base58(BIP84_ZPUB).unwrap(),
+        ];
+        let multisig = Multisig::new(2, xpubs)
+            .unwrap()
+            .derive_non_hardened(0)
+            .unwrap();
+
+        let address = multisig.to_address(MultisigType::P2wsh).unwrap();
+        let (_, program) = bech32::decode_segwit_address("bc", &address).unwrap();
+        assert_eq!(
+            hex(&multisig.script_pubkey(MultisigType::P2wsh)),
+            format!("0020{}", hex(&program)),
+            "P2WSH scriptPubKey mismatch"
+        );
+
+        for multisig_type in [MultisigType::P2sh, MultisigType::P2shP2wsh] {
+            let address = multisig.to_address(multisig_type).unwrap();
+            let payload = base58check::decode_check(&address).unwrap();
+            assert_eq!(payload[0], Network::Bitcoin.p2sh_prefix());
+            assert_eq!(
+                hex(&multisig.script_pubkey(multisig_type)),
+                format!("a914{}87", hex(&payload[1..])),
+                "{} scriptPubKey mismatch",
+                multisig_type
+            );
+        }
+    }
+
+    // Descriptor Key Path Tests
+
+    #[test]
+    fn test_descriptor_key_full_path() {
+        let key: DescriptorKey = format!("[73c5da0a/84'/0'/0']{}/1/*", BIP84_ZPUB)
+            .parse()
+            .unwrap();
+        assert_eq!(key.full_path().to_string(), "m/84'/0'/0'/1/*");
+        assert_eq!(key.full_path().child(7).to_string(), "m/84'/0'/0'/1/7");
+
+        let key: DescriptorKey = format!("{}/0/3", BIP84_ZPUB).parse().unwrap();
+        assert_eq!(
+            key.full_path().to_string(),
+            "m/0/3",
+            "Keys without an origin use the path below the key"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use bip_tools::{base58check, Multisig, MultisigType, Script, ScriptType, Xpub};
+
+#[cfg(test)]
+mod script_tests {
+    use super::*;
+
+    // BIP49 account key m/49'/0'/0' and the P2SH-P2WPKH address of m/49'/0'/0'/0/0
+    const BIP49_YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+    const BIP49_ADDRESS: &str = "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf";
+
+    // BIP84 account key m/84'/0'/0'
+    const BIP84_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    // 20 byte hash used in the BIP173 examples
+    const HASH: &str = "751e76e8199196d454941c45d1b3a323f1433bd6";
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn hash() -> [u8; 20] {
+        from_hex(HASH).try_into().unwrap()
+    }
+
+    fn receive_key(xpub: &str) -> Xpub {
+        Xpub::from_base58(xpub)
+            .unwrap()
+            .derive_path(&"0/0".parse().unwrap())
+            .unwrap()
+    }
+
+    // Standard Script Tests
+
+    #[test]
+    fn test_standard_scripts() {
+        let cases = [
+            (
+                Script::p2pkh(&hash()),
+                format!("76a914{}88ac", HASH),
+                format!("OP_DUP OP_HASH160 {} OP_EQUALVERIFY OP_CHECKSIG", HASH),
+            ),
+            (
+                Script::p2sh(&hash()),
+                format!("a914{}87", HASH),
+                format!("OP_HASH160 {} OP_EQUAL", HASH),
+            ),
+            (
+                Script::p2wpkh(&hash()),
+                format!("0014{}", HASH),
+                format!("0 {}", HASH),
+            ),
+            (
+                Script::witness_program(16, &hash()[..2]),
+                "6002751e".to_string(),
+                "16 751e".to_string(),
+            ),
+        ];
+        for (script, hex, asm) in cases {
+            assert_eq!(script.to_hex(), hex, "Script bytes mismatch");
+            assert_eq!(script.to_asm(), asm, "Assembly mismatch");
+            assert_eq!(script.to_string(), hex, "Display should write hex");
+        }
+
+        let output_key = [0x79; 32];
+        assert_eq!(
+            Script::p2tr(&output_key).to_asm(),
+            format!("1 {}", "79".repeat(32))
+        );
+    }
+
+    #[test]
+    fn test_script_wrapping() {
+        let script = Script::p2wpkh(&hash());
+        let p2wsh = script.to_p2wsh();
+        assert_eq!(p2wsh.len(), 34, "P2WSH programs are 32 byte hashes");
+        assert_eq!(p2wsh.to_asm().split(' ').next(), Some("0"));
+
+        let p2sh = script.to_p2sh();
+        assert_eq!(p2sh.len(), 23, "P2SH scripts commit to a 20 byte hash");
+        assert!(p2sh.to_asm().starts_with("OP_HASH160 "));
+        assert_eq!(
+            p2sh[2..22],
+            script.script_hash(),
+            "P2SH scripts should commit to the script hash"
+        );
+    }
+
+    // Assembly Tests
+
+    #[test]
+    fn test_asm_pushes_and_opcodes() {
+        let cases = [
+            ("", ""),
+            ("00", "0"),
+            ("4f", "-1"),
+            ("6a0568656c6c6f", "OP_RETURN 68656c6c6f"),
+            ("4c03aabbcc", "aabbcc"),
+            ("4d0200aabb", "aabb"),
+            ("4e01000000ff", "ff"),
+            ("b175", "OP_CHECKLOCKTIMEVERIFY OP_DROP"),
+            ("ff", "OP_UNKNOWN"),
+        ];
+        for (hex, asm) in cases {
+            assert_eq!(Script::new(from_hex(hex)).to_asm(), asm, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn test_asm_truncated_push() {
+        for hex in ["14aabb", "4c", "4c05aabb", "4d01"] {
+            assert!(
+                Script::new(from_hex(hex)).to_asm().ends_with("[error]"),
+                "{} should report a truncated push",
+                hex
+            );
+        }
+        assert_eq!(Script::new(from_hex("7603aa")).to_asm(), "OP_DUP [error]");
+    }
+
+    // Derived Key Tests
+
+    #[test]
+    fn test_key_redeem_scripts() {
+        let key = receive_key(BIP49_YPUB);
+        assert_eq!(key.to_address(ScriptType::P2shP2wpkh), BIP49_ADDRESS);
+
+        let redeem_script = key.redeem_script(ScriptType::P2shP2wpkh).unwrap();
+        assert_eq!(
+            redeem_script,
+            key.script_pubkey(ScriptType::P2wpkh),
+            "The P2SH-P2WPKH redeem script is the P2WPKH script"
+        );
+        assert_eq!(
+            redeem_script.to_p2sh(),
+            key.script_pubkey(ScriptType::P2shP2wpkh)
+        );
+
+        let payload = base58check::decode_check(BIP49_ADDRESS).unwrap();
+        assert_eq!(
+            &key.script_pubkey(ScriptType::P2shP2wpkh)[2..22],
+            &payload[1..],
+            "scriptPubKey should commit to the address hash"
+        );
+
+        for script_type in [ScriptType::P2pkh, ScriptType::P2wpkh, ScriptType::P2tr] {
+            assert_eq!(key.redeem_script(script_type), None, "{}", script_type);
+        }
+    }
+
+    #[test]
+    fn test_multisig_redeem_and_witness_scripts() {
+        let xpubs = vec![
+            Xpub::from_base58(BIP49_YPUB).unwrap(),
+            Xpub::from_base58(BIP84_ZPUB).unwrap(),
+        ];
+        let multisig = Multisig::new(2, xpubs)
+            .unwrap()
+            .derive_non_hardened(0)
+            .unwrap();
+        let script = multisig.script();
+        assert!(script.to_asm().starts_with("2 "));
+        assert!(script.to_asm().ends_with(" 2 OP_CHECKMULTISIG"));
+
+        assert_eq!(
+            multisig.redeem_script(MultisigType::P2sh),
+            Some(script.clone())
+        );
+        assert_eq!(multisig.witness_script(MultisigType::P2sh), None);
+
+        assert_eq!(
+            multisig.redeem_script(MultisigType::P2shP2wsh),
+            Some(script.to_p2wsh())
+        );
+        assert_eq!(
+            multisig.witness_script(MultisigType::P2shP2wsh),
+            Some(script.clone())
+        );
+
+        assert_eq!(multisig.redeem_script(MultisigType::P2wsh), None);
+        assert_eq!(multisig.witness_script(MultisigType::P2wsh), Some(script));
+
+        for multisig_type in MultisigType::ALL {
+            let script_pubkey = multisig.script_pubkey(multisig_type);
+            let expected = match multisig.redeem_script(multisig_type) {
+                Some(redeem_script) => redeem_script.to_p2sh(),
+                None => multisig.witness_script(multisig_type).unwrap().to_p2wsh(),
+            };
+            assert_eq!(script_pubkey, expected, "{} scriptPubKey", multisig_type);
+        }
+    }
+
+    #[test]
+    fn test_script_conversions() {
+        let bytes = from_hex(&format!("0014{}", HASH));
+        let script = Script::from(bytes.clone());
+        assert_eq!(script.as_bytes(), bytes.as_slice());
+        assert_eq!(script.len(), 22, "Scripts deref to their bytes");
+        assert_eq!(Vec::from(script.clone()), bytes);
+        assert_eq!(script.into_bytes(), bytes);
+    }
+}